@@ -1,6 +1,6 @@
 use crate::Result;
 use log::debug;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs;
 
 /// Default timeout for IPC connections in milliseconds
@@ -15,6 +15,36 @@ pub const DEFAULT_MAX_RECONNECT_ATTEMPTS: u32 = 5;
 /// Default reconnection delay in milliseconds
 pub const DEFAULT_RECONNECT_DELAY_MS: u64 = 500;
 
+/// Upper bound for the exponential backoff used while waiting for the socket file, in milliseconds
+pub const DEFAULT_BACKOFF_CAP_MS: u64 = 1000;
+
+/// How the delay between connection attempts grows, starting from `reconnect_delay_ms`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackoffStrategy {
+    /// Wait `reconnect_delay_ms` between every attempt
+    Fixed,
+    /// Double the delay after each attempt, up to `cap_ms`
+    Exponential { cap_ms: u64 },
+    /// Add `step_ms` to the delay after each attempt
+    Linear { step_ms: u64 },
+}
+
+impl BackoffStrategy {
+    /// Returns the delay to wait after an attempt that was preceded by `delay_ms`
+    pub fn next_delay_ms(&self, delay_ms: u64) -> u64 {
+        match *self {
+            BackoffStrategy::Fixed => delay_ms,
+            BackoffStrategy::Exponential { cap_ms } => std::cmp::min(delay_ms.saturating_mul(2), cap_ms),
+            BackoffStrategy::Linear { step_ms } => delay_ms.saturating_add(step_ms),
+        }
+    }
+    
+    /// Returns the delay before retry number `retry` (zero-based), starting from `base_ms`
+    pub fn delay_ms(&self, base_ms: u64, retry: u32) -> u64 {
+        (0..retry).fold(base_ms, |delay_ms, _| self.next_delay_ms(delay_ms))
+    }
+}
+
 /// IPC configuration options
 #[derive(Debug, Clone)]
 pub struct IpcConfig {
@@ -32,6 +62,11 @@ pub struct IpcConfig {
     
     /// Delay between reconnection attempts in milliseconds
     pub reconnect_delay_ms: u64,
+    
+    /// How the reconnection delay grows with each failed attempt. When unset, the delay
+    /// doubles (capped at `DEFAULT_BACKOFF_CAP_MS`) while the socket file does not exist
+    /// yet and stays at `reconnect_delay_ms` after failed connections and between reconnects
+    pub backoff: Option<BackoffStrategy>,
 }
 
 impl Default for IpcConfig {
@@ -42,6 +77,7 @@ impl Default for IpcConfig {
             auto_reconnect: true,
             max_reconnect_attempts: DEFAULT_MAX_RECONNECT_ATTEMPTS,
             reconnect_delay_ms: DEFAULT_RECONNECT_DELAY_MS,
+            backoff: None,
         }
     }
 }
@@ -61,6 +97,7 @@ impl IpcConfig {
             auto_reconnect,
             max_reconnect_attempts,
             reconnect_delay_ms,
+            backoff: None,
         }
     }
     
@@ -72,6 +109,7 @@ impl IpcConfig {
             auto_reconnect: false,
             max_reconnect_attempts: 0,
             reconnect_delay_ms: DEFAULT_RECONNECT_DELAY_MS,
+            backoff: None,
         }
     }
     
@@ -83,8 +121,25 @@ impl IpcConfig {
             auto_reconnect: true,
             max_reconnect_attempts: 10,
             reconnect_delay_ms: 250,
+            backoff: None,
         }
     }
+    
+    /// Returns this configuration with a different reconnection backoff strategy
+    pub fn with_backoff(mut self, backoff: BackoffStrategy) -> Self {
+        self.backoff = Some(backoff);
+        self
+    }
+    
+    /// Backoff applied while waiting for mpv to create the socket file
+    pub fn socket_wait_backoff(&self) -> BackoffStrategy {
+        self.backoff.unwrap_or(BackoffStrategy::Exponential { cap_ms: DEFAULT_BACKOFF_CAP_MS })
+    }
+    
+    /// Backoff applied after a failed connection and between reconnection attempts
+    pub fn retry_backoff(&self) -> BackoffStrategy {
+        self.backoff.unwrap_or(BackoffStrategy::Fixed)
+    }
 }
 
 /// Ensures the IPC socket directory exists
@@ -106,9 +161,15 @@ pub fn ensure_ipc_socket_dir() -> Result<PathBuf> {
     Ok(socket_dir)
 }
 
-/// Cleans up old IPC sockets
-pub fn cleanup_old_ipc_sockets() -> Result<()> {
+/// Cleans up old IPC sockets, returning how many were removed
+pub fn cleanup_old_ipc_sockets() -> Result<usize> {
     let socket_dir = ensure_ipc_socket_dir()?;
+    cleanup_ipc_sockets_in(&socket_dir)
+}
+
+/// Removes leftover `mpv-socket-*` files from `socket_dir`, returning how many were removed
+pub fn cleanup_ipc_sockets_in(socket_dir: &Path) -> Result<usize> {
+    let mut removed = 0;
     
     if cfg!(target_family = "unix") {
         // On Unix, look for socket files with the format "mpv-socket-*"
@@ -118,7 +179,8 @@ pub fn cleanup_old_ipc_sockets() -> Result<()> {
             if let Ok(entry) = entry {
                 let path = entry.path();
                 
-                if path.is_file() {
+                // Sockets are not regular files, so only skip directories
+                if !path.is_dir() {
                     if let Some(filename) = path.file_name() {
                         if let Some(filename_str) = filename.to_str() {
                             if filename_str.starts_with("mpv-socket-") {
@@ -127,6 +189,7 @@ pub fn cleanup_old_ipc_sockets() -> Result<()> {
                                     debug!("Failed to remove old socket file {}: {}", path.display(), e);
                                 } else {
                                     debug!("Removed old socket file: {}", path.display());
+                                    removed += 1;
                                 }
                             }
                         }
@@ -138,5 +201,48 @@ pub fn cleanup_old_ipc_sockets() -> Result<()> {
     
     // On Windows, named pipes are automatically cleaned up by the OS
     
-    Ok(())
-} 
\ No newline at end of file
+    Ok(removed)
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn fixed_backoff_keeps_the_base_delay() {
+        assert_eq!(BackoffStrategy::Fixed.next_delay_ms(500), 500);
+        assert_eq!(BackoffStrategy::Fixed.delay_ms(500, 0), 500);
+        assert_eq!(BackoffStrategy::Fixed.delay_ms(500, 4), 500);
+    }
+    
+    #[test]
+    fn linear_backoff_adds_a_step_per_retry() {
+        let backoff = BackoffStrategy::Linear { step_ms: 100 };
+        assert_eq!(backoff.next_delay_ms(250), 350);
+        assert_eq!(backoff.delay_ms(250, 0), 250);
+        assert_eq!(backoff.delay_ms(250, 3), 550);
+    }
+    
+    #[test]
+    fn exponential_backoff_doubles_up_to_the_cap() {
+        let backoff = BackoffStrategy::Exponential { cap_ms: 1000 };
+        assert_eq!(backoff.next_delay_ms(300), 600);
+        assert_eq!(backoff.delay_ms(300, 0), 300);
+        assert_eq!(backoff.delay_ms(300, 1), 600);
+        assert_eq!(backoff.delay_ms(300, 2), 1000);
+        assert_eq!(backoff.delay_ms(300, 10), 1000);
+        assert_eq!(backoff.next_delay_ms(u64::MAX), 1000);
+    }
+    
+    #[test]
+    fn default_backoff_only_grows_while_waiting_for_the_socket() {
+        let config = IpcConfig::default();
+        assert_eq!(config.socket_wait_backoff(), BackoffStrategy::Exponential { cap_ms: DEFAULT_BACKOFF_CAP_MS });
+        assert_eq!(config.retry_backoff(), BackoffStrategy::Fixed);
+        
+        let linear = BackoffStrategy::Linear { step_ms: 50 };
+        let config = IpcConfig::default().with_backoff(linear);
+        assert_eq!(config.socket_wait_backoff(), linear);
+        assert_eq!(config.retry_backoff(), linear);
+    }
+}
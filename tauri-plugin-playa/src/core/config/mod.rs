@@ -1,7 +1,7 @@
 use crate::Result;
 use log::{debug, info};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub mod ipc;
 
@@ -23,13 +23,74 @@ pub fn get_mpv_config_path() -> PathBuf {
     crate::core::get_assets_path()
 }
 
-/// Initializes the default configuration
-pub fn initialize_default_config() -> Result<()> {
+/// Summary of what `initialize_config` set up
+#[derive(Debug, Clone)]
+pub struct InitReport {
+    /// The mpv configuration directory
+    pub config_dir: PathBuf,
+    /// Number of leftover IPC sockets removed (many suggest earlier crashes)
+    pub sockets_cleaned: usize,
+    /// Whether the configuration directory had to be created
+    pub created_dir: bool,
+}
+
+/// Initializes the default configuration and reports what was done
+pub fn initialize_config() -> Result<InitReport> {
+    let socket_dir = ipc::ensure_ipc_socket_dir()?;
+    initialize_config_in(&get_mpv_config_path(), &socket_dir)
+}
+
+/// Initializes the configuration in the given config and socket directories
+pub fn initialize_config_in(config_dir: &Path, socket_dir: &Path) -> Result<InitReport> {
     // Ensure the config directory exists
-    ensure_config_dir()?;
+    let created_dir = !config_dir.exists();
+    if created_dir {
+        debug!("Creating config directory: {}", config_dir.display());
+        fs::create_dir_all(config_dir)?;
+    }
     
     // Initialize IPC configuration
-    ipc::cleanup_old_ipc_sockets()?;
+    let sockets_cleaned = ipc::cleanup_ipc_sockets_in(socket_dir)?;
     
-    Ok(())
-} 
\ No newline at end of file
+    info!("Initialized config in {} ({} old sockets removed)", config_dir.display(), sockets_cleaned);
+    
+    Ok(InitReport {
+        config_dir: config_dir.to_path_buf(),
+        sockets_cleaned,
+        created_dir,
+    })
+}
+
+/// Initializes the default configuration
+pub fn initialize_default_config() -> Result<()> {
+    initialize_config().map(|_| ())
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+    
+    #[cfg(unix)]
+    #[test]
+    fn init_report_reflects_created_dir_and_cleaned_sockets() {
+        let root = std::env::temp_dir().join(format!("playa-init-{}", Uuid::new_v4()));
+        let config_dir = root.join("mpv_config");
+        let socket_dir = root.join("sockets");
+        fs::create_dir_all(&socket_dir).unwrap();
+        for name in ["mpv-socket-1", "mpv-socket-2", "mpv-socket-3", "other.sock"] {
+            fs::write(socket_dir.join(name), "").unwrap();
+        }
+        
+        let first = initialize_config_in(&config_dir, &socket_dir).unwrap();
+        let second = initialize_config_in(&config_dir, &socket_dir).unwrap();
+        let other_kept = socket_dir.join("other.sock").exists();
+        let _ = fs::remove_dir_all(&root);
+        
+        assert!(first.created_dir);
+        assert_eq!(first.config_dir, config_dir);
+        assert_eq!(first.sockets_cleaned, 3);
+        assert!(!second.created_dir);
+        assert_eq!(second.sockets_cleaned, 0);
+        assert!(other_kept);
+    }
+}
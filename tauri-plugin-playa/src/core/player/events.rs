@@ -2,13 +2,47 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use log::{debug, error};
 
-use crate::core::player::ipc::MpvIpcClient;
+use crate::core::config::ipc::IpcConfig;
+use crate::core::player::ipc::{MpvIpcClient, parse_track_id};
 use crate::Error;
 use crate::Result;
 
+/// Why mpv stopped playing a file, as reported by the `end-file` event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CloseReason {
+    /// The end of the file was reached
+    Eof,
+    /// Playback was stopped by a command or a new file being loaded
+    Stop,
+    /// The player is quitting
+    Quit,
+    /// The file could not be played
+    Error,
+    /// The file was a playlist or redirect that was expanded
+    Redirect,
+    /// A reason this crate does not know about
+    Unknown,
+}
+
+impl CloseReason {
+    /// Maps mpv's `reason` string to a close reason.
+    pub fn from_mpv(reason: &str) -> Self {
+        match reason {
+            "eof" => CloseReason::Eof,
+            "stop" => CloseReason::Stop,
+            "quit" => CloseReason::Quit,
+            "error" => CloseReason::Error,
+            "redirect" => CloseReason::Redirect,
+            _ => CloseReason::Unknown,
+        }
+    }
+}
+
 /// Types of events that can be emitted by mpv.
 #[derive(Debug, Clone)]
 pub enum MpvEvent {
@@ -26,11 +60,27 @@ pub enum MpvEvent {
     VolumeChanged(i32),
     MuteChanged(bool),
     
+    // Track events (`-1` when no track is selected)
+    AudioTrackChanged(i64),
+    SubtitleTrackChanged(i64),
+    
+    // Cache events (dispatched as "cache")
+    Buffering { percent: f64 },
+    BufferingDone,
+    
     // Error events
     PlaybackError(String),
     
     // Process events
     ProcessExited(i32),
+    FileEnded { reason: CloseReason, error: Option<String>, playlist_entry_id: Option<i64> },
+    
+    // Seek events
+    SeekStarted,
+    PlaybackRestarted,
+    
+    // Script events
+    ClientMessage { args: Vec<String> },
     
     // Property change events
     PropertyChanged(String, Value),
@@ -40,9 +90,116 @@ pub enum MpvEvent {
     ConnectionRestored,
 }
 
+impl MpvEvent {
+    /// Parses a raw event message from mpv's IPC socket.
+    /// Returns the event name along with the parsed event, if it is one the crate understands.
+    /// Property changes are named after the property so they reach its subscribers.
+    pub fn from_ipc(message: &Value) -> Option<(String, MpvEvent)> {
+        let name = message.get("event")?.as_str()?;
+        
+        let event = match name {
+            "property-change" => {
+                let property = message.get("name")?.as_str()?;
+                let data = message.get("data").cloned().unwrap_or(Value::Null);
+                let track_id = || parse_track_id(&data).map(|id| id.unwrap_or(-1));
+                match property {
+                    "aid" => return Some((property.to_string(), MpvEvent::AudioTrackChanged(track_id()?))),
+                    "sid" => return Some((property.to_string(), MpvEvent::SubtitleTrackChanged(track_id()?))),
+                    _ => {},
+                }
+                return Some((property.to_string(), MpvEvent::PropertyChanged(property.to_string(), data)));
+            },
+            "end-file" => {
+                let reason = message.get("reason")
+                    .and_then(Value::as_str)
+                    .map(CloseReason::from_mpv)
+                    .unwrap_or(CloseReason::Unknown);
+                let error = message.get("file_error")
+                    .and_then(Value::as_str)
+                    .map(|error| error.to_string());
+                let playlist_entry_id = message.get("playlist_entry_id").and_then(Value::as_i64);
+                MpvEvent::FileEnded { reason, error, playlist_entry_id }
+            },
+            "seek" => MpvEvent::SeekStarted,
+            "playback-restart" => MpvEvent::PlaybackRestarted,
+            "client-message" => {
+                let args = message.get("args")
+                    .and_then(Value::as_array)
+                    .map(|args| args.iter()
+                        .filter_map(|arg| arg.as_str().map(|arg| arg.to_string()))
+                        .collect())
+                    .unwrap_or_default();
+                MpvEvent::ClientMessage { args }
+            },
+            _ => return None,
+        };
+        
+        Some((name.to_string(), event))
+    }
+}
+
 /// Callback type for mpv events.
 pub type EventCallback = Arc<dyn Fn(MpvEvent) + Send + Sync + 'static>;
 
+/// Change-detection state of a listener's poll thread.
+#[derive(Debug, Default)]
+struct PollState {
+    /// When the last position update was sent
+    last_position_update: Option<Instant>,
+    /// Last reported time position, to avoid sending too many updates
+    last_time_pos: Option<f64>,
+    /// Last reported percent position
+    last_percent_pos: Option<f64>,
+    /// Last observed pause state
+    last_pause_state: Option<bool>,
+    /// Properties registered via `observe_custom_property`, with their last reported value
+    custom_properties: HashMap<String, Option<Value>>,
+    /// Whether playback is currently stalled waiting for the cache
+    buffering: bool,
+    /// Last reported cache fill level, from `cache-buffering-state`
+    cache_percent: f64,
+}
+
+impl PollState {
+    /// Records a new value of a custom property, returning whether it changed.
+    /// Always true for properties that are not custom ones.
+    fn update_custom_property(&mut self, name: &str, value: &Value) -> bool {
+        match self.custom_properties.get_mut(name) {
+            Some(last) if last.as_ref() == Some(value) => false,
+            Some(last) => {
+                *last = Some(value.clone());
+                true
+            },
+            None => true,
+        }
+    }
+    
+    /// Tracks the cache properties, returning the cache event to report for a change, if any
+    fn update_cache_state(&mut self, property: &str, value: &Value) -> Option<MpvEvent> {
+        match property {
+            "paused-for-cache" => match value.as_bool()? {
+                true if !self.buffering => {
+                    self.buffering = true;
+                    Some(MpvEvent::Buffering { percent: self.cache_percent })
+                },
+                false if self.buffering => {
+                    self.buffering = false;
+                    Some(MpvEvent::BufferingDone)
+                },
+                _ => None,
+            },
+            "cache-buffering-state" => {
+                let percent = value.as_f64()?;
+                let changed = percent != self.cache_percent;
+                self.cache_percent = percent;
+                // The fill level is reported during normal playback too, only forward it while stalled
+                (self.buffering && changed).then_some(MpvEvent::Buffering { percent })
+            },
+            _ => None,
+        }
+    }
+}
+
 /// Event listener for mpv events.
 pub struct MpvEventListener {
     ipc_client: Arc<Mutex<MpvIpcClient>>,
@@ -52,6 +209,7 @@ pub struct MpvEventListener {
     poll_thread: Option<JoinHandle<()>>,
     connection_status: Arc<Mutex<bool>>,
     last_reconnect_attempt: Arc<Mutex<Option<Instant>>>,
+    poll_state: Arc<Mutex<PollState>>,
 }
 
 impl MpvEventListener {
@@ -65,6 +223,7 @@ impl MpvEventListener {
             poll_thread: None,
             connection_status: Arc::new(Mutex::new(true)), // Assume connected initially
             last_reconnect_attempt: Arc::new(Mutex::new(None)),
+            poll_state: Arc::new(Mutex::new(PollState::default())),
         }
     }
     
@@ -75,7 +234,7 @@ impl MpvEventListener {
     {
         let event_callback = Arc::new(callback);
         
-        if ["time-pos", "percent-pos", "pause", "mute", "volume", "eof-reached", "idle-active"]
+        if ["time-pos", "percent-pos", "pause", "mute", "volume", "eof-reached", "idle-active", "video-params", "metadata", "metadata/by-key/icy-title"]
             .contains(&event_type) {
             
             // Automatically observe the property if it's one of the standard properties
@@ -90,6 +249,14 @@ impl MpvEventListener {
         Ok(())
     }
     
+    /// Observes any mpv property, e.g. `ab-loop-a`, `sub-delay` or `video-params/aspect`.
+    /// Changes are delivered as `MpvEvent::PropertyChanged` to subscribers of the property name.
+    pub fn observe_custom_property(&mut self, name: &str) -> Result<()> {
+        self.observe_property(name)?;
+        self.poll_state.lock().unwrap().custom_properties.entry(name.to_string()).or_insert(None);
+        Ok(())
+    }
+    
     /// Observes a property in mpv.
     fn observe_property(&mut self, property: &str) -> Result<()> {
         let mut property_observers = self.property_observers.lock().unwrap();
@@ -129,24 +296,43 @@ impl MpvEventListener {
         *running = true;
         drop(running);
         
+        // Track selection and buffering are always reported, so `all` subscribers see them too
+        for property in ["aid", "sid", "paused-for-cache", "cache-buffering-state"] {
+            if let Err(e) = self.observe_property(property) {
+                debug!("Failed to observe {}: {}", property, e);
+            }
+        }
+        
         let ipc_client = Arc::clone(&self.ipc_client);
         let callbacks = Arc::clone(&self.callbacks);
         let property_observers = Arc::clone(&self.property_observers);
         let running = Arc::clone(&self.running);
         let connection_status = Arc::clone(&self.connection_status);
         let last_reconnect_attempt = Arc::clone(&self.last_reconnect_attempt);
+        let poll_state = Arc::clone(&self.poll_state);
         
         // Start a thread to poll for events
         let poll_thread = thread::spawn(move || {
             debug!("Starting event polling thread");
+            let mut last_reconnections = ipc_client.lock().unwrap().reconnections();
             
             while *running.lock().unwrap() {
                 // Handle connection status
-                let is_connected = {
+                let (is_connected, reconnections) = {
                     let ipc_client = ipc_client.lock().unwrap();
-                    ipc_client.is_connected()
+                    (ipc_client.is_connected(), ipc_client.reconnections())
                 };
                 
+                // A command may have reconnected while retrying, without the status ever reading as lost
+                if is_connected && reconnections != last_reconnections {
+                    last_reconnections = reconnections;
+                    if *connection_status.lock().unwrap() {
+                        Self::notify_callbacks(&callbacks, "connection", &MpvEvent::ConnectionRestored);
+                        debug!("Connection to mpv re-established during a command");
+                        Self::reobserve_properties(&ipc_client, &property_observers);
+                    }
+                }
+                
                 {
                     let mut current_status = connection_status.lock().unwrap();
                     if *current_status != is_connected {
@@ -199,7 +385,7 @@ impl MpvEventListener {
                 }
                 
                 // Poll for events if connected
-                Self::poll_events(&ipc_client, &callbacks, &property_observers);
+                Self::poll_events(&ipc_client, &callbacks, &property_observers, &poll_state);
                 
                 // Use the configured poll interval instead of hardcoded value
                 let poll_interval = {
@@ -311,6 +497,7 @@ impl MpvEventListener {
         ipc_client: &Arc<Mutex<MpvIpcClient>>,
         callbacks: &Arc<Mutex<HashMap<String, Vec<EventCallback>>>>,
         _property_observers: &Arc<Mutex<HashMap<String, u64>>>,
+        poll_state: &Arc<Mutex<PollState>>,
     ) {
         // Try to acquire the lock on the IPC client
         let mut ipc_client = match ipc_client.try_lock() {
@@ -327,49 +514,90 @@ impl MpvEventListener {
             return;
         }
         
-        // Track when the last position update was sent
-        static mut LAST_POSITION_UPDATE: Option<Instant> = None;
+        let mut poll_state = poll_state.lock().unwrap();
         
         // Check if we need to update position (every 3 seconds)
-        let should_update_position = unsafe {
-            match LAST_POSITION_UPDATE {
-                None => true,
-                Some(last_time) => last_time.elapsed() >= Duration::from_secs(3)
-            }
+        let should_update_position = match poll_state.last_position_update {
+            None => true,
+            Some(last_time) => last_time.elapsed() >= Duration::from_secs(3)
         };
         
         // Only update playback position occasionally
         if should_update_position {
-            unsafe { LAST_POSITION_UPDATE = Some(Instant::now()) };
-            Self::update_playback_properties(&mut ipc_client, callbacks);
+            poll_state.last_position_update = Some(Instant::now());
+            Self::update_playback_properties(&mut ipc_client, callbacks, &mut poll_state);
         }
         
         // Always check for critical events
         Self::check_eof(&mut ipc_client, callbacks);
-        Self::check_state_changes(&mut ipc_client, callbacks);
+        Self::check_state_changes(&mut ipc_client, callbacks, &mut poll_state);
+        Self::check_custom_properties(&mut ipc_client, callbacks, &mut poll_state);
+        
+        // Forward events mpv pushed to us while the properties were queried
+        Self::dispatch_events(&mut ipc_client, callbacks, &mut poll_state);
+    }
+    
+    /// Forwards the events buffered by the IPC client to callbacks registered under the event name
+    fn dispatch_events(
+        ipc_client: &mut MpvIpcClient,
+        callbacks: &Arc<Mutex<HashMap<String, Vec<EventCallback>>>>,
+        poll_state: &mut PollState,
+    ) {
+        for message in ipc_client.take_pending_events() {
+            if let Some((name, event)) = MpvEvent::from_ipc(&message) {
+                if let MpvEvent::PropertyChanged(property, value) = &event {
+                    if let Some(cache_event) = poll_state.update_cache_state(property, value) {
+                        Self::notify_callbacks(callbacks, "cache", &cache_event);
+                    }
+                    
+                    // Custom properties are also polled, so skip values that were already reported
+                    if !poll_state.update_custom_property(property, value) {
+                        continue;
+                    }
+                }
+                
+                debug!("Received mpv event: {}", name);
+                Self::notify_callbacks(callbacks, &name, &event);
+            }
+        }
+    }
+    
+    /// Reads the custom properties and notifies subscribers of the ones that changed
+    fn check_custom_properties(
+        ipc_client: &mut MpvIpcClient,
+        callbacks: &Arc<Mutex<HashMap<String, Vec<EventCallback>>>>,
+        poll_state: &mut PollState,
+    ) {
+        let names: Vec<String> = poll_state.custom_properties.keys().cloned().collect();
+        
+        for name in names {
+            // Unavailable properties (e.g. video-params before playback starts) are skipped
+            let Ok(value) = ipc_client.get_property(&name) else {
+                continue;
+            };
+            
+            if poll_state.update_custom_property(&name, &value) {
+                Self::notify_callbacks(callbacks, &name, &MpvEvent::PropertyChanged(name.clone(), value));
+            }
+        }
     }
     
     /// Updates playback properties like time-pos and percent-pos
     fn update_playback_properties(
         ipc_client: &mut MpvIpcClient,
         callbacks: &Arc<Mutex<HashMap<String, Vec<EventCallback>>>>,
+        poll_state: &mut PollState,
     ) {
-        // Track the last reported positions to avoid sending too many updates
-        static mut LAST_TIME_POS: Option<f64> = None;
-        static mut LAST_PERCENT_POS: Option<f64> = None;
-        
         // Get the current playback position
         if let Ok(time_pos) = ipc_client.get_time_pos() {
             // Only notify if position changed by at least 5 seconds
-            let should_notify = unsafe {
-                match LAST_TIME_POS {
-                    None => true,
-                    Some(last_pos) => (time_pos - last_pos).abs() >= 5.0
-                }
+            let should_notify = match poll_state.last_time_pos {
+                None => true,
+                Some(last_pos) => (time_pos - last_pos).abs() >= 5.0
             };
             
             if should_notify {
-                unsafe { LAST_TIME_POS = Some(time_pos) };
+                poll_state.last_time_pos = Some(time_pos);
                 Self::notify_callbacks(callbacks, "time-pos", &MpvEvent::TimePositionChanged(time_pos));
             }
         }
@@ -377,15 +605,13 @@ impl MpvEventListener {
         // Get the current percentage position
         if let Ok(percent_pos) = ipc_client.get_percent_pos() {
             // Only notify if position changed by at least 1%
-            let should_notify = unsafe {
-                match LAST_PERCENT_POS {
-                    None => true,
-                    Some(last_pos) => (percent_pos - last_pos).abs() >= 1.0
-                }
+            let should_notify = match poll_state.last_percent_pos {
+                None => true,
+                Some(last_pos) => (percent_pos - last_pos).abs() >= 1.0
             };
             
             if should_notify {
-                unsafe { LAST_PERCENT_POS = Some(percent_pos) };
+                poll_state.last_percent_pos = Some(percent_pos);
                 Self::notify_callbacks(callbacks, "percent-pos", &MpvEvent::PercentPositionChanged(percent_pos));
             }
         }
@@ -466,12 +692,11 @@ impl MpvEventListener {
     fn check_state_changes(
         ipc_client: &mut MpvIpcClient,
         callbacks: &Arc<Mutex<HashMap<String, Vec<EventCallback>>>>,
+        poll_state: &mut PollState,
     ) {
         // Check pause state
         if let Ok(paused) = ipc_client.get_pause() {
-            static mut LAST_PAUSE_STATE: Option<bool> = None;
-            
-            let last_state = unsafe { LAST_PAUSE_STATE };
+            let last_state = poll_state.last_pause_state;
             
             if last_state != Some(paused) {
                 if paused {
@@ -484,7 +709,7 @@ impl MpvEventListener {
                     Self::notify_callbacks(callbacks, "pause", &MpvEvent::PlaybackStarted);
                 }
                 
-                unsafe { LAST_PAUSE_STATE = Some(paused); }
+                poll_state.last_pause_state = Some(paused);
             }
         }
         
@@ -514,6 +739,11 @@ impl MpvEventListener {
         }
     }
     
+    /// Replaces the IPC configuration of the listener's connection, e.g. its poll interval.
+    pub fn update_ipc_config(&self, config: IpcConfig) {
+        self.ipc_client.lock().unwrap().update_config(config);
+    }
+    
     /// Checks if the event listener is running.
     pub fn is_running(&self) -> bool {
         *self.running.lock().unwrap()
@@ -553,4 +783,90 @@ impl MpvEventListener {
         debug!("Process exit handling completed");
         Ok(())
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    
+    #[test]
+    fn close_reasons_map_from_mpv() {
+        assert_eq!(CloseReason::from_mpv("eof"), CloseReason::Eof);
+        assert_eq!(CloseReason::from_mpv("stop"), CloseReason::Stop);
+        assert_eq!(CloseReason::from_mpv("quit"), CloseReason::Quit);
+        assert_eq!(CloseReason::from_mpv("error"), CloseReason::Error);
+        assert_eq!(CloseReason::from_mpv("redirect"), CloseReason::Redirect);
+        assert_eq!(CloseReason::from_mpv("something-new"), CloseReason::Unknown);
+    }
+    
+    #[test]
+    fn parses_end_file_with_an_error() {
+        let message = json!({
+            "event": "end-file",
+            "reason": "error",
+            "playlist_entry_id": 2,
+            "file_error": "loading failed",
+        });
+        
+        let (name, event) = MpvEvent::from_ipc(&message).unwrap();
+        
+        assert_eq!(name, "end-file");
+        assert!(matches!(
+            event,
+            MpvEvent::FileEnded { reason: CloseReason::Error, error: Some(error), playlist_entry_id: Some(2) }
+                if error == "loading failed"
+        ));
+    }
+    
+    #[test]
+    fn parses_end_file_without_a_reason() {
+        let (_, event) = MpvEvent::from_ipc(&json!({"event": "end-file"})).unwrap();
+        assert!(matches!(event, MpvEvent::FileEnded { reason: CloseReason::Unknown, error: None, playlist_entry_id: None }));
+    }
+    
+    #[test]
+    fn parses_client_message_args() {
+        let (name, event) = MpvEvent::from_ipc(&json!({"event": "client-message", "args": ["playa-button", "pin"]})).unwrap();
+        
+        assert_eq!(name, "client-message");
+        assert!(matches!(event, MpvEvent::ClientMessage { args } if args == ["playa-button", "pin"]));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn listeners_keep_independent_poll_state() {
+        use crate::core::player::mock::{MockMpv, wait_until};
+        
+        let start = |time_pos: f64, paused: bool| {
+            let mock = MockMpv::start();
+            mock.set("time-pos", json!(time_pos));
+            mock.set("pause", json!(paused));
+            let mut listener = MpvEventListener::new(mock.client());
+            let events = Arc::new(Mutex::new(Vec::new()));
+            for event_type in ["time-pos", "pause"] {
+                let events = Arc::clone(&events);
+                listener.subscribe(event_type, move |event| events.lock().unwrap().push(event)).unwrap();
+            }
+            listener.start_listening().unwrap();
+            (mock, listener, events)
+        };
+        let (_first_mock, mut first, first_events) = start(10.0, false);
+        let (_second_mock, mut second, second_events) = start(100.0, true);
+        
+        // Shared state would hold back the second position update and flip-flop the pause state
+        assert!(wait_until(2000, || first_events.lock().unwrap().len() >= 2 && second_events.lock().unwrap().len() >= 2));
+        std::thread::sleep(Duration::from_millis(500));
+        first.stop_listening().unwrap();
+        second.stop_listening().unwrap();
+        
+        let first_events = first_events.lock().unwrap();
+        assert_eq!(first_events.len(), 2, "{:?}", first_events);
+        assert!(first_events.iter().any(|event| matches!(event, MpvEvent::TimePositionChanged(time_pos) if *time_pos == 10.0)));
+        assert!(first_events.iter().any(|event| matches!(event, MpvEvent::PlaybackStarted)));
+        
+        let second_events = second_events.lock().unwrap();
+        assert_eq!(second_events.len(), 2, "{:?}", second_events);
+        assert!(second_events.iter().any(|event| matches!(event, MpvEvent::TimePositionChanged(time_pos) if *time_pos == 100.0)));
+        assert!(second_events.iter().any(|event| matches!(event, MpvEvent::PlaybackPaused)));
+    }
+}
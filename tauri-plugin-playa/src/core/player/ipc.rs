@@ -1,9 +1,13 @@
 use crate::{Error, Result};
 use log::{debug, error};
+use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
-use std::io::{Write, BufRead, BufReader};
+use std::collections::{HashMap, VecDeque};
+use std::io::{ErrorKind, Read, Write};
+use std::path::Path;
 use std::time::{Duration, Instant};
 use crate::core::config::ipc::IpcConfig;
+use crate::core::player::process::HrSeek;
 
 #[cfg(target_family = "unix")]
 use std::os::unix::net::UnixStream;
@@ -27,6 +31,461 @@ use std::ptr;
 #[cfg(target_family = "windows")]
 use std::io;
 
+/// A chapter entry from mpv's `chapter-list` property
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChapterInfo {
+    /// Chapter title, if the media provides one
+    pub title: Option<String>,
+    /// Chapter start time in seconds
+    pub time: f64,
+}
+
+impl ChapterInfo {
+    /// Parses a chapter entry from mpv's JSON representation
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let time = value.get("time")?.as_f64()?;
+        let title = value.get("title").and_then(|t| t.as_str()).map(|t| t.to_string());
+        
+        Some(Self { title, time })
+    }
+}
+
+/// A track from mpv's `track-list`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Track {
+    /// Track ID, as used by `aid`/`sid`/`vid`
+    pub id: i64,
+    /// Track type (`video`, `audio` or `sub`)
+    #[serde(rename = "type")]
+    pub track_type: String,
+    /// Track title, if the media provides one
+    pub title: Option<String>,
+    /// Language code (usually ISO 639-1 or 639-2)
+    pub lang: Option<String>,
+    /// Codec name
+    pub codec: Option<String>,
+    /// Whether the track is flagged as default
+    pub default: bool,
+    /// Whether the track is currently selected
+    pub selected: bool,
+}
+
+impl Track {
+    /// Parses a track entry from mpv's JSON representation
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let string = |key: &str| value.get(key).and_then(|v| v.as_str()).map(|v| v.to_string());
+        let flag = |key: &str| value.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+        
+        Some(Self {
+            id: value.get("id")?.as_i64()?,
+            track_type: string("type")?,
+            title: string("title"),
+            lang: string("lang"),
+            codec: string("codec"),
+            default: flag("default"),
+            selected: flag("selected"),
+        })
+    }
+    
+    /// Returns a label for the track, falling back from the title to the
+    /// language name, then the codec, then `Track {id}`
+    pub fn display_name(&self) -> String {
+        if let Some(title) = self.title.as_deref().filter(|t| !t.is_empty()) {
+            return title.to_string();
+        }
+        if let Some(lang) = self.lang.as_deref().filter(|l| !l.is_empty()) {
+            return language_name(lang).map(|name| name.to_string()).unwrap_or_else(|| lang.to_string());
+        }
+        if let Some(codec) = self.codec.as_deref().filter(|c| !c.is_empty()) {
+            return codec.to_string();
+        }
+        format!("Track {}", self.id)
+    }
+}
+
+/// A track of a known type, as listed by `VideoManager::get_audio_tracks` and `get_subtitle_tracks`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackInfo {
+    /// Track ID, as used by `aid`/`sid`
+    pub id: i64,
+    /// Track title, if the media provides one
+    pub title: Option<String>,
+    /// Language code (usually ISO 639-1 or 639-2)
+    pub lang: Option<String>,
+    /// Codec name
+    pub codec: Option<String>,
+    /// Whether the track is flagged as default
+    pub default: bool,
+    /// Whether the track is currently selected
+    pub selected: bool,
+}
+
+impl From<Track> for TrackInfo {
+    fn from(track: Track) -> Self {
+        Self {
+            id: track.id,
+            title: track.title,
+            lang: track.lang,
+            codec: track.codec,
+            default: track.default,
+            selected: track.selected,
+        }
+    }
+}
+
+/// An entry from mpv's `playlist`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaylistEntry {
+    /// File path or URL of the entry
+    pub filename: String,
+    /// Title, if the playlist or media provides one
+    pub title: Option<String>,
+    /// Whether this is the entry mpv considers current
+    pub current: bool,
+    /// Whether this entry is being played
+    pub playing: bool,
+}
+
+impl PlaylistEntry {
+    /// Parses a playlist entry from mpv's JSON representation
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let flag = |key: &str| value.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+        
+        Some(Self {
+            filename: value.get("filename")?.as_str()?.to_string(),
+            title: value.get("title").and_then(|v| v.as_str()).map(|v| v.to_string()),
+            current: flag("current"),
+            playing: flag("playing"),
+        })
+    }
+}
+
+/// Maps a common ISO 639-1 or 639-2 language code to its English name
+pub fn language_name(code: &str) -> Option<&'static str> {
+    let name = match code.to_lowercase().as_str() {
+        "en" | "eng" => "English",
+        "fr" | "fre" | "fra" => "French",
+        "de" | "ger" | "deu" => "German",
+        "es" | "spa" => "Spanish",
+        "it" | "ita" => "Italian",
+        "pt" | "por" => "Portuguese",
+        "nl" | "dut" | "nld" => "Dutch",
+        "sv" | "swe" => "Swedish",
+        "no" | "nor" => "Norwegian",
+        "da" | "dan" => "Danish",
+        "fi" | "fin" => "Finnish",
+        "pl" | "pol" => "Polish",
+        "cs" | "cze" | "ces" => "Czech",
+        "hu" | "hun" => "Hungarian",
+        "el" | "gre" | "ell" => "Greek",
+        "tr" | "tur" => "Turkish",
+        "ru" | "rus" => "Russian",
+        "uk" | "ukr" => "Ukrainian",
+        "ar" | "ara" => "Arabic",
+        "he" | "heb" => "Hebrew",
+        "hi" | "hin" => "Hindi",
+        "th" | "tha" => "Thai",
+        "vi" | "vie" => "Vietnamese",
+        "id" | "ind" => "Indonesian",
+        "ja" | "jpn" => "Japanese",
+        "ko" | "kor" => "Korean",
+        "zh" | "chi" | "zho" => "Chinese",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Returns the start and end of a chapter, where the last chapter ends at `duration`
+pub fn chapter_loop_bounds(chapters: &[ChapterInfo], index: usize, duration: f64) -> Option<(f64, f64)> {
+    let start = chapters.get(index)?.time;
+    let end = chapters.get(index + 1).map(|next| next.time).unwrap_or(duration);
+    
+    if end > start {
+        Some((start, end))
+    } else {
+        None
+    }
+}
+
+/// Debanding filter settings for the GPU video output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DebandParams {
+    /// Whether debanding is enabled
+    pub enabled: bool,
+    /// Number of debanding steps (1-16)
+    pub iterations: u32,
+    /// Cut-off threshold; higher values deband more but lose detail (0-4096)
+    pub threshold: u32,
+    /// Initial radius in pixels (1-64)
+    pub range: u32,
+    /// Strength of the noise added to hide banding (0-4096)
+    pub grain: u32,
+}
+
+impl Default for DebandParams {
+    fn default() -> Self {
+        // mpv's defaults
+        Self {
+            enabled: true,
+            iterations: 1,
+            threshold: 48,
+            range: 16,
+            grain: 32,
+        }
+    }
+}
+
+/// Zoom and pan applied to the video, e.g. by touch gestures
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct VideoTransform {
+    /// Zoom as a log2 factor (0 = no zoom, 1 = twice the size)
+    pub zoom: f64,
+    /// Horizontal pan, relative to the video width
+    pub pan_x: f64,
+    /// Vertical pan, relative to the video height
+    pub pan_y: f64,
+}
+
+/// Repeat behavior, mapped to mpv's `loop-file` and `loop-playlist` properties
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LoopMode {
+    /// Play through once
+    #[default]
+    Off,
+    /// Repeat the current file the given number of times (0 = forever)
+    File(u32),
+    /// Repeat the whole playlist the given number of times (0 = forever)
+    Playlist(u32),
+}
+
+impl LoopMode {
+    /// Returns the `loop-file` and `loop-playlist` values for this mode
+    fn as_mpv_values(&self) -> (Value, Value) {
+        let count = |times: u32| if times == 0 { json!("inf") } else { json!(times) };
+        match self {
+            LoopMode::Off => (json!("no"), json!("no")),
+            LoopMode::File(times) => (count(*times), json!("no")),
+            LoopMode::Playlist(times) => (json!("no"), count(*times)),
+        }
+    }
+}
+
+/// How `loadfile` treats the current playlist
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LoadMode {
+    /// Stop the current file and play the new one
+    Replace,
+    /// Add the file to the end of the playlist
+    Append,
+    /// Add the file to the end of the playlist and play it if nothing is playing
+    AppendPlay,
+}
+
+impl LoadMode {
+    /// Returns the flag used for mpv's `loadfile` command
+    pub fn as_mpv_value(&self) -> &'static str {
+        match self {
+            LoadMode::Replace => "replace",
+            LoadMode::Append => "append",
+            LoadMode::AppendPlay => "append-play",
+        }
+    }
+}
+
+/// Audio output channel layout, mapped to mpv's `audio-channels` property
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AudioChannels {
+    /// Use every channel the output device supports
+    Auto,
+    /// Use the device's layout only where mpv considers it safe, otherwise stereo
+    AutoSafe,
+    /// Downmix to two channels
+    Stereo,
+    /// Downmix to a single channel
+    Mono,
+    /// Any other mpv channel layout or list of layouts (e.g. `5.1,stereo`)
+    Custom(String),
+}
+
+impl AudioChannels {
+    /// Returns the value used for mpv's `audio-channels` property
+    pub fn as_mpv_value(&self) -> &str {
+        match self {
+            AudioChannels::Auto => "auto",
+            AudioChannels::AutoSafe => "auto-safe",
+            AudioChannels::Stereo => "stereo",
+            AudioChannels::Mono => "mono",
+            AudioChannels::Custom(layout) => layout,
+        }
+    }
+    
+    /// Maps an `audio-channels` value reported by mpv back to a layout
+    pub fn from_mpv_value(value: &str) -> Self {
+        match value {
+            "auto" => AudioChannels::Auto,
+            "auto-safe" => AudioChannels::AutoSafe,
+            "stereo" => AudioChannels::Stereo,
+            "mono" => AudioChannels::Mono,
+            other => AudioChannels::Custom(other.to_string()),
+        }
+    }
+}
+
+/// Precision of a seek
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SeekMode {
+    /// Fast seek using the instance's `hr-seek` setting, keyframe-aligned by default
+    #[default]
+    KeyframeFast,
+    /// Decode up to the exact position, for frame-accurate scrubbing
+    Exact,
+}
+
+/// What an mpv instance is currently doing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlaybackState {
+    Playing,
+    Paused,
+    /// No file is loaded
+    Idle,
+    /// Playback is stalled waiting for the cache to fill
+    Buffering,
+}
+
+impl PlaybackState {
+    /// Returns the lowercase name of the state
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PlaybackState::Playing => "playing",
+            PlaybackState::Paused => "paused",
+            PlaybackState::Idle => "idle",
+            PlaybackState::Buffering => "buffering",
+        }
+    }
+}
+
+/// Demuxer cache statistics, e.g. for graphing network health while streaming
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct CacheStats {
+    /// How full the cache is relative to what mpv waits for before resuming (0-100)
+    pub buffering_percent: f64,
+    /// Seconds of media buffered ahead of the playback position
+    pub cache_duration_secs: f64,
+    /// Timestamp in seconds up to which the media is buffered
+    pub cache_time_secs: f64,
+}
+
+/// Structured mpv version parsed from the `mpv-version` property
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MpvVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    /// Commit hash for git builds (e.g. `mpv git-abc1234` or `mpv v0.38.0-12-gabc1234`)
+    pub git_hash: Option<String>,
+}
+
+impl MpvVersion {
+    /// Parses a version string such as `mpv 0.38.0` or `mpv git-abc1234`.
+    /// Pure git builds have no release number and report `0.0.0`.
+    pub fn parse(version: &str) -> Result<Self> {
+        let token = version
+            .trim()
+            .trim_start_matches("mpv")
+            .split_whitespace()
+            .next()
+            .ok_or_else(|| Error::MpvError(format!("Invalid mpv version: {}", version)))?;
+        
+        if let Some(hash) = token.strip_prefix("git-") {
+            return Ok(Self { major: 0, minor: 0, patch: 0, git_hash: Some(hash.to_string()) });
+        }
+        
+        let mut parts = token.trim_start_matches('v').split('-');
+        let release = parts.next().unwrap_or_default();
+        let git_hash = parts
+            .filter_map(|part| part.strip_prefix('g'))
+            .find(|hash| hash.len() >= 7 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+            .map(|hash| hash.to_string());
+        
+        let numbers: Vec<&str> = release.split('.').collect();
+        let parse_part = |index: usize| -> Result<u32> {
+            match numbers.get(index) {
+                Some(part) => part.parse::<u32>()
+                    .map_err(|_| Error::MpvError(format!("Invalid mpv version: {}", version))),
+                None if index == 2 => Ok(0),
+                None => Err(Error::MpvError(format!("Invalid mpv version: {}", version))),
+            }
+        };
+        
+        Ok(Self {
+            major: parse_part(0)?,
+            minor: parse_part(1)?,
+            patch: parse_part(2)?,
+            git_hash,
+        })
+    }
+    
+    /// Returns whether this version is at least the given release.
+    /// Pure git builds are assumed to be recent and always satisfy the check.
+    pub fn at_least(&self, major: u32, minor: u32, patch: u32) -> bool {
+        if self.major == 0 && self.minor == 0 && self.patch == 0 && self.git_hash.is_some() {
+            return true;
+        }
+        (self.major, self.minor, self.patch) >= (major, minor, patch)
+    }
+}
+
+/// Maximum number of unread events buffered by a client before the oldest are dropped
+const MAX_PENDING_EVENTS: usize = 256;
+
+/// Properties collected by `MpvIpcClient::dump_state` for diagnostics
+pub const DIAGNOSTIC_PROPERTIES: &[&str] = &[
+    "mpv-version",
+    "path",
+    "file-format",
+    "duration",
+    "time-pos",
+    "pause",
+    "speed",
+    "volume",
+    "hwdec",
+    "hwdec-current",
+    "current-vo",
+    "current-ao",
+    "vo-passes",
+    "video-params",
+    "video-out-params",
+    "audio-params",
+    "avsync",
+    "frame-drop-count",
+    "decoder-frame-drop-count",
+    "demuxer-cache-state",
+    "cache-speed",
+    "estimated-vf-fps",
+];
+
+/// Profiles defined by mpv itself (builtin.conf) rather than by user or bundled config files
+pub const BUILTIN_PROFILES: &[&str] = &[
+    "default",
+    "pseudo-gui",
+    "builtin-pseudo-gui",
+    "libmpv",
+    "encoding",
+    "gpu-hq",
+    "opengl-hq",
+    "high-quality",
+    "fast",
+    "low-latency",
+    "sw-fast",
+    "box",
+    "sub-box",
+    "osd-box",
+];
+
 /// Client for communicating with mpv via JSON IPC.
 pub struct MpvIpcClient {
     #[cfg(target_family = "unix")]
@@ -42,6 +501,12 @@ pub struct MpvIpcClient {
     reconnect_attempts: u32,
     last_reconnect_time: Option<Instant>,
     intentionally_closed: bool,
+    /// Number of times the connection has been re-established
+    reconnections: u64,
+    /// Bytes read from the socket that do not yet form a complete line
+    read_buffer: Vec<u8>,
+    /// Events received while waiting for command responses
+    pending_events: VecDeque<Value>,
 }
 
 impl MpvIpcClient {
@@ -69,10 +534,10 @@ impl MpvIpcClient {
                            attempts + 1, max_attempts);
                     std::thread::sleep(std::time::Duration::from_millis(delay_ms));
                     attempts += 1;
-                    delay_ms = std::cmp::min(delay_ms * 2, 1000); // Exponential backoff, capped at 1 second
+                    delay_ms = config.socket_wait_backoff().next_delay_ms(delay_ms);
                     
                     if attempts >= max_attempts {
-                        return Err(Error::MpvError(format!("Socket path not found after {} attempts", max_attempts)));
+                        return Err(Error::ConnectionTimeout { socket_path: socket_path.to_string(), attempts });
                     }
                     continue;
                 }
@@ -92,17 +557,22 @@ impl MpvIpcClient {
                             reconnect_attempts: 0,
                             last_reconnect_time: None,
                             intentionally_closed: false,
+                            reconnections: 0,
+                            read_buffer: Vec::new(),
+                            pending_events: VecDeque::new(),
                         });
                     },
                     Err(e) => {
+                        attempts += 1;
                         if attempts >= max_attempts {
-                            error!("Failed to connect to mpv IPC socket after {} attempts: {}", max_attempts, e);
-                            return Err(Error::Io(e.to_string()));
+                            error!("Failed to connect to mpv IPC socket after {} attempts: {}", attempts, e);
+                            return Err(Error::ConnectionTimeout { socket_path: socket_path.to_string(), attempts });
                         }
                         
                         debug!("Failed to connect to mpv IPC socket, retrying ({}/{}): {}", 
-                               attempts + 1, max_attempts, e);
+                               attempts, max_attempts, e);
                         std::thread::sleep(std::time::Duration::from_millis(delay_ms));
+                        delay_ms = config.retry_backoff().next_delay_ms(delay_ms);
                     }
                 }
             }
@@ -121,17 +591,22 @@ impl MpvIpcClient {
                             reconnect_attempts: 0,
                             last_reconnect_time: None,
                             intentionally_closed: false,
+                            reconnections: 0,
+                            read_buffer: Vec::new(),
+                            pending_events: VecDeque::new(),
                         });
                     },
                     Err(e) => {
+                        attempts += 1;
                         if attempts >= max_attempts {
-                            error!("Failed to connect to mpv IPC socket after {} attempts: {}", max_attempts, e);
-                            return Err(Error::Io(e.to_string()));
+                            error!("Failed to connect to mpv IPC socket after {} attempts: {}", attempts, e);
+                            return Err(Error::ConnectionTimeout { socket_path: socket_path.to_string(), attempts });
                         }
                         
                         debug!("Failed to connect to mpv IPC socket, retrying ({}/{}): {}", 
-                               attempts + 1, max_attempts, e);
+                               attempts, max_attempts, e);
                         std::thread::sleep(std::time::Duration::from_millis(delay_ms));
+                        delay_ms = config.retry_backoff().next_delay_ms(delay_ms);
                     }
                 }
             }
@@ -169,11 +644,14 @@ impl MpvIpcClient {
         
         let now = Instant::now();
         
-        // If we recently tried to reconnect, wait a bit to avoid hammering the socket
+        // If we recently tried to reconnect, back off to avoid hammering the socket
         if let Some(last_time) = self.last_reconnect_time {
             let elapsed = now.duration_since(last_time);
-            if elapsed < Duration::from_millis(self.config.reconnect_delay_ms) {
-                std::thread::sleep(Duration::from_millis(self.config.reconnect_delay_ms) - elapsed);
+            let delay = Duration::from_millis(
+                self.config.retry_backoff().delay_ms(self.config.reconnect_delay_ms, self.reconnect_attempts - 1)
+            );
+            if elapsed < delay {
+                std::thread::sleep(delay - elapsed);
             }
         }
         
@@ -198,7 +676,9 @@ impl MpvIpcClient {
             match UnixStream::connect(&self.socket_path) {
                 Ok(socket) => {
                     self.socket = socket;
+                    self.read_buffer.clear();
                     self.connected = true;
+                    self.reconnections += 1;
                     self.reset_reconnect_attempts();
                     debug!("Successfully reconnected to mpv IPC socket");
                     return Ok(());
@@ -226,7 +706,9 @@ impl MpvIpcClient {
             match std::fs::OpenOptions::new().read(true).write(true).open(&self.socket_path) {
                 Ok(socket) => {
                     self.socket = socket;
+                    self.read_buffer.clear();
                     self.connected = true;
+                    self.reconnections += 1;
                     self.reset_reconnect_attempts();
                     debug!("Successfully reconnected to mpv IPC socket");
                     return Ok(());
@@ -282,7 +764,47 @@ impl MpvIpcClient {
         result
     }
     
+    /// Sends a command with named arguments, e.g. `{"name": "loadfile", "url": "video.mkv"}`,
+    /// with automatic reconnection if configured.
+    pub fn named_command(&mut self, command: Value) -> Result<Value> {
+        let result = self.named_command_internal(&command);
+        
+        if let Err(ref e) = result {
+            if self.should_reconnect(e) {
+                debug!("Named command failed, attempting to reconnect and retry");
+                match self.reconnect() {
+                    Ok(_) => {
+                        return self.named_command_internal(&command);
+                    },
+                    Err(reconnect_err) => {
+                        error!("Failed to reconnect: {}", reconnect_err);
+                        return Err(reconnect_err);
+                    }
+                }
+            }
+        } else {
+            self.reset_reconnect_attempts();
+        }
+        
+        result
+    }
+    
+    /// Internal implementation of named_command without reconnection logic
+    fn named_command_internal(&mut self, command: &Value) -> Result<Value> {
+        let id = self.request_id;
+        self.request_id += 1;
+        
+        let request = json!({
+            "command": command,
+            "request_id": id
+        });
+        
+        self.send_request(&request)?;
+        self.receive_response(id)
+    }
+    
     /// Internal implementation of command without reconnection logic
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, args), fields(socket = %self.socket_path)))]
     fn command_internal(&mut self, command: &str, args: &[Value]) -> Result<Value> {
         let id = self.request_id;
         self.request_id += 1;
@@ -324,6 +846,7 @@ impl MpvIpcClient {
     }
     
     /// Internal implementation of get_property without reconnection logic
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self), fields(socket = %self.socket_path)))]
     fn get_property_internal(&mut self, property: &str) -> Result<Value> {
         let id = self.request_id;
         self.request_id += 1;
@@ -337,6 +860,65 @@ impl MpvIpcClient {
         self.receive_response(id)
     }
     
+    /// Gets several properties in one round trip by pipelining the requests.
+    /// A property mpv cannot provide is returned as `{"error": "<message>"}` rather
+    /// than failing the whole batch; connection errors still fail (and reconnect as configured).
+    pub fn get_properties(&mut self, properties: &[&str]) -> Result<HashMap<String, Value>> {
+        let result = self.get_properties_internal(properties);
+        
+        if let Err(ref e) = result {
+            if self.should_reconnect(e) {
+                debug!("Get properties failed, attempting to reconnect and retry");
+                match self.reconnect() {
+                    Ok(_) => {
+                        return self.get_properties_internal(properties);
+                    },
+                    Err(reconnect_err) => {
+                        error!("Failed to reconnect: {}", reconnect_err);
+                        return Err(reconnect_err);
+                    }
+                }
+            }
+        } else {
+            self.reset_reconnect_attempts();
+        }
+        
+        result
+    }
+    
+    /// Internal implementation of get_properties without reconnection logic
+    fn get_properties_internal(&mut self, properties: &[&str]) -> Result<HashMap<String, Value>> {
+        let mut ids = Vec::with_capacity(properties.len());
+        
+        for property in properties {
+            let id = self.request_id;
+            self.request_id += 1;
+            
+            let request = json!({
+                "command": ["get_property", property],
+                "request_id": id
+            });
+            
+            self.send_request(&request)?;
+            ids.push((id, *property));
+        }
+        
+        let mut values = HashMap::with_capacity(ids.len());
+        for (id, property) in ids {
+            match self.receive_response(id) {
+                Ok(value) => {
+                    values.insert(property.to_string(), value);
+                },
+                Err(Error::MpvError(e)) => {
+                    values.insert(property.to_string(), json!({ "error": e }));
+                },
+                Err(e) => return Err(e),
+            }
+        }
+        
+        Ok(values)
+    }
+    
     /// Sets a property in mpv with automatic reconnection if configured.
     pub fn set_property(&mut self, property: &str, value: Value) -> Result<Value> {
         let result = self.set_property_internal(property, value.clone());
@@ -362,6 +944,7 @@ impl MpvIpcClient {
     }
     
     /// Internal implementation of set_property without reconnection logic
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self, value), fields(socket = %self.socket_path)))]
     fn set_property_internal(&mut self, property: &str, value: Value) -> Result<Value> {
         let id = self.request_id;
         self.request_id += 1;
@@ -375,6 +958,45 @@ impl MpvIpcClient {
         self.receive_response(id)
     }
     
+    /// Sets several properties in one round trip by pipelining the requests.
+    /// Every request is sent before the responses are read; the first failure is returned.
+    pub fn set_properties(&mut self, properties: &[(&str, Value)]) -> Result<()> {
+        match self.set_properties_collect(properties)?.into_iter().next() {
+            Some((_, e)) => Err(e),
+            None => Ok(()),
+        }
+    }
+    
+    /// Sets several properties in one pipelined round trip like `set_properties`,
+    /// returning every property mpv rejected along with its error.
+    /// Fails outright only if the requests could not be sent.
+    pub fn set_properties_collect(&mut self, properties: &[(&str, Value)]) -> Result<Vec<(String, Error)>> {
+        let mut ids = Vec::with_capacity(properties.len());
+        
+        for (property, value) in properties {
+            let id = self.request_id;
+            self.request_id += 1;
+            
+            let request = json!({
+                "command": ["set_property", property, value],
+                "request_id": id
+            });
+            
+            self.send_request(&request)?;
+            ids.push(id);
+        }
+        
+        let mut failures = Vec::new();
+        for ((property, _), id) in properties.iter().zip(ids) {
+            // Read every response, even after a failure, so none are left in the socket
+            if let Err(e) = self.receive_response(id) {
+                failures.push((property.to_string(), e));
+            }
+        }
+        
+        Ok(failures)
+    }
+    
     /// Observes a property in mpv with automatic reconnection if configured.
     pub fn observe_property(&mut self, property: &str) -> Result<u64> {
         let result = self.observe_property_internal(property);
@@ -410,17 +1032,9 @@ impl MpvIpcClient {
         });
         
         self.send_request(&request)?;
-        if let Value::Object(response) = self.receive_response(id)? {
-            if let Some(Value::String(error)) = response.get("error") {
-                if error != "success" {
-                    return Err(Error::MpvError(error.clone()));
-                }
-            }
-            
-            return Ok(id);
-        }
+        self.receive_response(id)?;
         
-        Err(Error::MpvError("Invalid response format".to_string()))
+        Ok(id)
     }
     
     /// Unobserves a property in mpv with automatic reconnection if configured.
@@ -508,40 +1122,44 @@ impl MpvIpcClient {
     /// Sends a request to mpv with improved error handling
     fn send_request(&mut self, request: &Value) -> Result<()> {
         if !self.connected {
-            return Err(Error::MpvError("Not connected to mpv".to_string()));
+            if self.config.auto_reconnect {
+                self.reconnect()?;
+            } else {
+                return Err(Error::MpvError("Not connected to mpv".to_string()));
+            }
         }
         
-        let request_str = request.to_string();
+        // Frame the whole request as a single line and write it in one go,
+        // so mpv never sees a request interleaved with another one
+        let mut request_bytes = request.to_string().into_bytes();
+        request_bytes.push(b'\n');
         
-        // Add a newline to the request
-        let request_bytes = format!("{}\n", request_str).into_bytes();
-        
-        #[cfg(target_family = "unix")] 
-        {
-            match self.socket.write_all(&request_bytes) {
-                Ok(_) => Ok(()),
-                Err(e) => {
-                    error!("Failed to send request: {}", e);
-                    self.connected = false;
-                    Err(Error::Io(e.to_string()))
-                }
-            }
+        let result = self.socket.write_all(&request_bytes).and_then(|_| self.socket.flush());
+        if let Err(e) = result {
+            error!("Failed to send request: {}", e);
+            // A failed write may have left part of the line in mpv's parser, which would
+            // corrupt the next request on this connection, so drop it and reconnect instead
+            self.drop_connection();
+            return Err(Error::Io(format!("connection reset after failed write: {}", e)));
         }
         
-        #[cfg(target_family = "windows")]
+        Ok(())
+    }
+    
+    /// Abandons the current connection so the next request reconnects on a fresh one
+    fn drop_connection(&mut self) {
+        self.connected = false;
+        self.read_buffer.clear();
+        
+        #[cfg(target_family = "unix")]
         {
-            match self.socket.write_all(&request_bytes) {
-                Ok(_) => Ok(()),
-                Err(e) => {
-                    error!("Failed to send request: {}", e);
-                    self.connected = false;
-                    Err(Error::Io(e.to_string()))
-                }
-            }
+            let _ = self.socket.shutdown(std::net::Shutdown::Both);
         }
     }
     
     /// Receives a response from mpv with improved error handling and timeout
+    ///
+    /// Events arriving before the response are queued and can be retrieved with `next_event`.
     fn receive_response(&mut self, request_id: u64) -> Result<Value> {
         if !self.connected {
             if self.config.auto_reconnect {
@@ -552,36 +1170,126 @@ impl MpvIpcClient {
         }
         
         let timeout = Duration::from_millis(self.config.timeout_ms);
-        let _start_time = Instant::now();
-        
-        #[cfg(target_family = "unix")]
-        let mut reader = BufReader::new(&self.socket);
+        let start_time = Instant::now();
         
-        #[cfg(target_family = "windows")]
-        let mut reader = BufReader::new(&self.socket);
+        loop {
+            let remaining = match timeout.checked_sub(start_time.elapsed()) {
+                Some(remaining) if !remaining.is_zero() => remaining,
+                _ => {
+                    debug!("Timed out waiting for response to request ID {}", request_id);
+                    return Err(Error::ConnectionTimeout { socket_path: self.socket_path.clone(), attempts: 1 });
+                },
+            };
+            
+            let line = match self.read_line(remaining)? {
+                Some(line) => line,
+                None => continue,
+            };
+            
+            let message: Value = match serde_json::from_str(&line) {
+                Ok(message) => message,
+                Err(_) => {
+                    debug!("Ignoring malformed line from mpv: {}", line);
+                    continue;
+                }
+            };
+            
+            if message.get("event").is_some() {
+                self.queue_event(message);
+                continue;
+            }
+            
+            if message.get("request_id").and_then(Value::as_u64) != Some(request_id) {
+                debug!("Ignoring response for another request: {}", line);
+                continue;
+            }
+            
+            debug!("Received response: {}", line);
+            return match message.get("error").and_then(Value::as_str) {
+                Some("success") | None => Ok(message.get("data").cloned().unwrap_or(Value::Null)),
+                Some(error) => Err(Error::MpvError(error.to_string())),
+            };
+        }
+    }
+    
+    /// Reads a single line from the socket, returning `None` if nothing arrived within `timeout`
+    fn read_line(&mut self, timeout: Duration) -> Result<Option<String>> {
+        loop {
+            if let Some(pos) = self.read_buffer.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = self.read_buffer.drain(..=pos).collect();
+                return Ok(Some(String::from_utf8_lossy(&line).trim().to_string()));
+            }
+            
+            #[cfg(target_family = "unix")]
+            {
+                self.socket.set_read_timeout(Some(timeout))
+                    .map_err(|e| Error::Io(e.to_string()))?;
+            }
+            
+            #[cfg(target_family = "windows")]
+            let _ = timeout;
+            
+            let mut chunk = [0u8; 4096];
+            match self.socket.read(&mut chunk) {
+                Ok(0) => {
+                    debug!("mpv closed the IPC connection");
+                    self.connected = false;
+                    return Err(Error::Io("connection reset by mpv".to_string()));
+                },
+                Ok(n) => self.read_buffer.extend_from_slice(&chunk[..n]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::TimedOut => {
+                    return Ok(None);
+                },
+                Err(e) => {
+                    error!("Failed to read response: {}", e);
+                    self.connected = false;
+                    return Err(Error::Io(e.to_string()));
+                }
+            }
+        }
+    }
+    
+    /// Buffers an event for later retrieval, dropping the oldest one when full
+    fn queue_event(&mut self, event: Value) {
+        if self.pending_events.len() >= MAX_PENDING_EVENTS {
+            self.pending_events.pop_front();
+        }
+        self.pending_events.push_back(event);
+    }
+    
+    /// Returns the next event sent by mpv, waiting up to `timeout` for one to arrive
+    pub fn next_event(&mut self, timeout: Duration) -> Result<Option<Value>> {
+        if let Some(event) = self.pending_events.pop_front() {
+            return Ok(Some(event));
+        }
         
-        // Set read timeout if available
-        #[cfg(target_family = "unix")]
-        {
-            self.socket.set_read_timeout(Some(timeout))
-                .map_err(|e| Error::Io(e.to_string()))?;
+        if !self.connected {
+            return Err(Error::MpvError("Not connected to mpv".to_string()));
         }
         
-        // Read the response
-        let mut response_str = String::new();
-        match reader.read_line(&mut response_str) {
-            Ok(_) => {
-                debug!("Received response: {}", response_str);
-            },
-            Err(e) => {
-                error!("Failed to read response: {}", e);
-                self.connected = false;
-                return Err(Error::Io(e.to_string()));
+        let start_time = Instant::now();
+        loop {
+            let remaining = match timeout.checked_sub(start_time.elapsed()) {
+                Some(remaining) if !remaining.is_zero() => remaining,
+                _ => return Ok(None),
+            };
+            
+            let line = match self.read_line(remaining)? {
+                Some(line) => line,
+                None => return Ok(None),
+            };
+            
+            if let Ok(message) = serde_json::from_str::<Value>(&line) {
+                if message.get("event").is_some() {
+                    return Ok(Some(message));
+                }
             }
         }
-        
-        // If we reach here, we've exhausted the reader without finding a matching response
-        Err(Error::MpvError(format!("No response found for request ID {}", request_id)))
+    }
+    
+    /// Takes all events received so far without reading from the socket
+    pub fn take_pending_events(&mut self) -> Vec<Value> {
+        self.pending_events.drain(..).collect()
     }
     
     /// Returns whether mpv is still running
@@ -652,6 +1360,12 @@ impl MpvIpcClient {
         self.connected
     }
     
+    /// Returns how many times the connection has been re-established, including
+    /// reconnects done transparently while retrying a command
+    pub fn reconnections(&self) -> u64 {
+        self.reconnections
+    }
+    
     /// Closes the connection to mpv.
     pub fn close(&mut self) {
         debug!("Explicitly closing IPC client connection");
@@ -729,6 +1443,20 @@ impl MpvIpcClient {
         }
     }
     
+    /// Gets the last A/V sync difference in seconds (positive when audio is ahead of video)
+    pub fn get_avsync(&mut self) -> Result<f64> {
+        match self.get_property("avsync")? {
+            Value::Number(n) => {
+                if let Some(avsync) = n.as_f64() {
+                    Ok(avsync)
+                } else {
+                    Err(Error::MpvError("Invalid avsync format".to_string()))
+                }
+            },
+            _ => Err(Error::MpvError("Invalid avsync type".to_string()))
+        }
+    }
+    
     /// Gets the current playback speed (1.0 is normal speed)
     pub fn get_speed(&mut self) -> Result<f64> {
         match self.get_property("speed")? {
@@ -748,6 +1476,11 @@ impl MpvIpcClient {
         self.set_property("speed", json!(speed))
     }
     
+    /// Enables or disables audio pitch correction for non-1.0 playback speeds
+    pub fn set_pitch_correction(&mut self, on: bool) -> Result<Value> {
+        self.set_property("audio-pitch-correction", json!(on))
+    }
+    
     /// Gets the current volume level (0-100)
     pub fn get_volume(&mut self) -> Result<f64> {
         match self.get_property("volume")? {
@@ -767,6 +1500,64 @@ impl MpvIpcClient {
         self.set_property("volume", json!(volume))
     }
     
+    /// Gets the subtitle delay in seconds
+    pub fn get_sub_delay(&mut self) -> Result<f64> {
+        match self.get_property("sub-delay")? {
+            Value::Number(n) => {
+                if let Some(delay) = n.as_f64() {
+                    Ok(delay)
+                } else {
+                    Err(Error::MpvError("Invalid sub-delay format".to_string()))
+                }
+            },
+            _ => Err(Error::MpvError("Invalid sub-delay type".to_string()))
+        }
+    }
+    
+    /// Sets the subtitle delay in seconds (positive delays subtitles)
+    pub fn set_sub_delay(&mut self, seconds: f64) -> Result<Value> {
+        self.set_property("sub-delay", json!(seconds))
+    }
+    
+    /// Gets the subtitle delay in milliseconds
+    pub fn get_sub_delay_ms(&mut self) -> Result<i64> {
+        Ok(seconds_to_ms(self.get_sub_delay()?))
+    }
+    
+    /// Sets the subtitle delay in milliseconds (positive delays subtitles)
+    pub fn set_sub_delay_ms(&mut self, ms: i64) -> Result<Value> {
+        self.set_sub_delay(ms_to_seconds(ms))
+    }
+    
+    /// Gets the audio delay in seconds
+    pub fn get_audio_delay(&mut self) -> Result<f64> {
+        match self.get_property("audio-delay")? {
+            Value::Number(n) => {
+                if let Some(delay) = n.as_f64() {
+                    Ok(delay)
+                } else {
+                    Err(Error::MpvError("Invalid audio-delay format".to_string()))
+                }
+            },
+            _ => Err(Error::MpvError("Invalid audio-delay type".to_string()))
+        }
+    }
+    
+    /// Sets the audio delay in seconds (positive delays audio)
+    pub fn set_audio_delay(&mut self, seconds: f64) -> Result<Value> {
+        self.set_property("audio-delay", json!(seconds))
+    }
+    
+    /// Gets the audio delay in milliseconds
+    pub fn get_audio_delay_ms(&mut self) -> Result<i64> {
+        Ok(seconds_to_ms(self.get_audio_delay()?))
+    }
+    
+    /// Sets the audio delay in milliseconds (positive delays audio)
+    pub fn set_audio_delay_ms(&mut self, ms: i64) -> Result<Value> {
+        self.set_audio_delay(ms_to_seconds(ms))
+    }
+    
     /// Gets the current mute state
     pub fn get_mute(&mut self) -> Result<bool> {
         match self.get_property("mute")? {
@@ -826,7 +1617,41 @@ impl MpvIpcClient {
     
     /// Seeks to a specific position in seconds
     pub fn seek(&mut self, position: f64) -> Result<Value> {
-        self.command("seek", &[json!(position), json!("absolute")])
+        self.seek_with_mode(position, SeekMode::KeyframeFast)
+    }
+    
+    /// Seeks to an absolute position with the given precision
+    pub fn seek_with_mode(&mut self, position: f64, mode: SeekMode) -> Result<Value> {
+        let flags = match mode {
+            SeekMode::KeyframeFast => "absolute",
+            SeekMode::Exact => "absolute+exact",
+        };
+        self.command("seek", &[json!(position), json!(flags)])
+    }
+    
+    /// Sets the default precision for subsequent seeks
+    pub fn set_hr_seek(&mut self, mode: HrSeek) -> Result<Value> {
+        self.set_property("hr-seek", json!(mode.as_mpv_value()))
+    }
+    
+    /// Loops playback between two positions in seconds
+    pub fn set_ab_loop(&mut self, a: f64, b: f64) -> Result<()> {
+        self.set_property("ab-loop-a", json!(a))?;
+        self.set_property("ab-loop-b", json!(b))?;
+        Ok(())
+    }
+    
+    /// Clears the A-B loop points
+    pub fn clear_ab_loop(&mut self) -> Result<()> {
+        self.set_property("ab-loop-a", json!("no"))?;
+        self.set_property("ab-loop-b", json!("no"))?;
+        Ok(())
+    }
+    
+    /// Seeks to a timecode such as `1:23:45`, `23:45.5` or `45`
+    pub fn seek_timecode(&mut self, timecode: &str) -> Result<Value> {
+        let position = parse_timecode(timecode)?;
+        self.seek(position)
     }
     
     /// Seeks to a specific percentage position (0-100)
@@ -847,6 +1672,12 @@ impl MpvIpcClient {
         }
     }
     
+    /// Gets the chapter list as typed entries
+    pub fn get_chapters(&mut self) -> Result<Vec<ChapterInfo>> {
+        let chapters = self.get_chapter_list()?;
+        Ok(chapters.iter().filter_map(ChapterInfo::from_value).collect())
+    }
+    
     /// Gets the current chapter index
     pub fn get_chapter(&mut self) -> Result<i64> {
         match self.get_property("chapter")? {
@@ -966,17 +1797,237 @@ impl MpvIpcClient {
         self.set_property("sid", json!(id))
     }
     
+    /// Gets the ID of the currently selected audio track, or `None` if no track is selected
+    pub fn get_current_audio_track(&mut self) -> Result<Option<i64>> {
+        let value = self.get_property("aid")?;
+        parse_track_id(&value)
+            .ok_or_else(|| Error::MpvError("Invalid aid type".to_string()))
+    }
+    
+    /// Gets the ID of the currently selected subtitle track, or `None` if subtitles are off
+    pub fn get_current_subtitle_track(&mut self) -> Result<Option<i64>> {
+        let value = self.get_property("sid")?;
+        parse_track_id(&value)
+            .ok_or_else(|| Error::MpvError("Invalid sid type".to_string()))
+    }
+    
+    /// Switches to the next audio track, like mpv's `#` key
+    pub fn cycle_audio_track(&mut self) -> Result<Value> {
+        self.command("cycle", &[json!("aid")])
+    }
+    
+    /// Switches to the next subtitle track, like mpv's `j` key
+    pub fn cycle_subtitle_track(&mut self) -> Result<Value> {
+        self.command("cycle", &[json!("sid")])
+    }
+    
     /// Disables subtitles
     pub fn disable_subtitles(&mut self) -> Result<Value> {
         self.set_property("sid", json!("no"))
     }
     
+    /// Gets the protocols supported by this mpv build (e.g. `http`, `rtsp`, `bd`)
+    pub fn get_protocols(&mut self) -> Result<Vec<String>> {
+        let value = self.get_property("protocol-list")?;
+        parse_string_list(&value)
+            .ok_or_else(|| Error::MpvError("Invalid protocol-list type".to_string()))
+    }
+    
+    /// Checks whether this mpv build supports a URL scheme, with or without the trailing `://`
+    pub fn supports_protocol(&mut self, scheme: &str) -> Result<bool> {
+        let scheme = scheme.trim_end_matches("://").to_lowercase();
+        Ok(self.get_protocols()?.iter().any(|p| p.to_lowercase() == scheme))
+    }
+    
+    /// Gets the libavformat demuxers available to this mpv build
+    pub fn get_demuxers(&mut self) -> Result<Vec<String>> {
+        let value = self.get_property("demuxer-lavf-list")?;
+        parse_string_list(&value)
+            .ok_or_else(|| Error::MpvError("Invalid demuxer-lavf-list type".to_string()))
+    }
+    
+    /// Gets the names of profiles defined in config files, excluding mpv's built-in ones
+    pub fn get_profiles(&mut self) -> Result<Vec<String>> {
+        match self.get_property("profile-list")? {
+            Value::Array(profiles) => Ok(parse_profile_names(&profiles)),
+            _ => Err(Error::MpvError("Invalid profile-list type".to_string()))
+        }
+    }
+    
+    /// Gets the structured mpv version from the `mpv-version` property
+    pub fn get_mpv_version(&mut self) -> Result<MpvVersion> {
+        match self.get_property("mpv-version")? {
+            Value::String(version) => MpvVersion::parse(&version),
+            _ => Err(Error::MpvError("Invalid mpv-version type".to_string()))
+        }
+    }
+    
+    /// Gets the mpv version string as reported by mpv, e.g. `mpv 0.38.0`.
+    /// The `get_version` command only reports the client API version, so this reads `mpv-version`.
+    pub fn get_mpv_version_string(&mut self) -> Result<String> {
+        match self.get_property("mpv-version")? {
+            Value::String(version) => Ok(version),
+            _ => Err(Error::MpvError("Invalid mpv-version type".to_string()))
+        }
+    }
+    
+    /// Clears runtime adjustments: speed, mute, volume, A-B loop, video filters,
+    /// video equalizer and audio/subtitle delays
+    pub fn reset_playback(&mut self) -> Result<()> {
+        self.set_properties(&[
+            ("speed", json!(1.0)),
+            ("mute", json!(false)),
+            ("volume", json!(100)),
+            ("ab-loop-a", json!("no")),
+            ("ab-loop-b", json!("no")),
+            ("brightness", json!(0)),
+            ("contrast", json!(0)),
+            ("saturation", json!(0)),
+            ("gamma", json!(0)),
+            ("hue", json!(0)),
+            ("audio-delay", json!(0.0)),
+            ("sub-delay", json!(0.0)),
+        ])?;
+        self.command("vf", &[json!("clr"), json!("")])?;
+        Ok(())
+    }
+    
+    /// Gets the current zoom and pan in a single batched call
+    pub fn get_transform(&mut self) -> Result<VideoTransform> {
+        let values = self.get_properties(&["video-zoom", "video-pan-x", "video-pan-y"])?;
+        let number = |property: &str| {
+            values.get(property)
+                .and_then(|v| v.as_f64())
+                .ok_or_else(|| Error::MpvError(format!("Invalid {} type", property)))
+        };
+        
+        Ok(VideoTransform {
+            zoom: number("video-zoom")?,
+            pan_x: number("video-pan-x")?,
+            pan_y: number("video-pan-y")?,
+        })
+    }
+    
+    /// Sets zoom and pan in a single batched call
+    pub fn set_transform(&mut self, transform: VideoTransform) -> Result<()> {
+        self.set_properties(&[
+            ("video-zoom", json!(transform.zoom)),
+            ("video-pan-x", json!(transform.pan_x)),
+            ("video-pan-y", json!(transform.pan_y)),
+        ])
+    }
+    
+    /// Applies debanding settings in a single batched call
+    pub fn set_deband(&mut self, params: DebandParams) -> Result<()> {
+        self.set_properties(&[
+            ("deband", json!(params.enabled)),
+            ("deband-iterations", json!(params.iterations)),
+            ("deband-threshold", json!(params.threshold)),
+            ("deband-range", json!(params.range)),
+            ("deband-grain", json!(params.grain)),
+        ])
+    }
+    
+    /// Sets the audio output channel layout, e.g. to downmix surround sound to stereo
+    pub fn set_audio_channels(&mut self, layout: AudioChannels) -> Result<Value> {
+        self.set_property("audio-channels", json!(layout.as_mpv_value()))
+    }
+    
+    /// Gets the audio output channel layout
+    pub fn get_audio_channels(&mut self) -> Result<AudioChannels> {
+        match self.get_property("audio-channels")? {
+            Value::String(layout) => Ok(AudioChannels::from_mpv_value(&layout)),
+            _ => Err(Error::MpvError("Invalid audio-channels type".to_string()))
+        }
+    }
+    
+    /// Loads a file or URL, replacing the current file or appending it to the playlist
+    pub fn load_file(&mut self, source: &str, mode: LoadMode) -> Result<Value> {
+        self.command("loadfile", &[json!(source), json!(mode.as_mpv_value())])
+    }
+    
+    /// Loads a file like `load_file`, with options such as `start` or `title` that apply to
+    /// this file only and are reset when the next one is loaded
+    pub fn load_file_with_options(&mut self, source: &str, mode: LoadMode, options: &[(&str, String)]) -> Result<Value> {
+        if options.is_empty() {
+            return self.load_file(source, mode);
+        }
+        
+        // Named arguments keep working across mpv versions that inserted an index argument,
+        // and `%len%` quoting lets values contain commas or equals signs
+        let options: Vec<String> = options.iter()
+            .map(|(name, value)| format!("{}=%{}%{}", name, value.len(), value))
+            .collect();
+        self.named_command(json!({
+            "name": "loadfile",
+            "url": source,
+            "flags": mode.as_mpv_value(),
+            "options": options.join(","),
+        }))
+    }
+    
+    /// Sets the loop mode, replacing any file or playlist loop already active
+    pub fn set_loop(&mut self, mode: LoopMode) -> Result<()> {
+        let (loop_file, loop_playlist) = mode.as_mpv_values();
+        self.set_properties(&[
+            ("loop-file", loop_file),
+            ("loop-playlist", loop_playlist),
+        ])
+    }
+    
+    /// Starts recording the stream to `path`, or stops recording when `None`
+    pub fn set_stream_record(&mut self, path: Option<&Path>) -> Result<Value> {
+        let target = path.map(|path| path.display().to_string()).unwrap_or_default();
+        self.set_property("stream-record", json!(target))
+    }
+    
+    /// Sets the OSD level (0 = none, 1 = messages only, 2 = with seek bar, 3 = with time)
+    pub fn set_osd_level(&mut self, level: i32) -> Result<Value> {
+        self.set_property("osd-level", json!(level))
+    }
+    
+    /// Reads a set of diagnostic properties into a JSON object for bug reports.
+    /// Properties mpv cannot provide (e.g. no video loaded) are recorded as `null`.
+    pub fn dump_state(&mut self) -> Result<Value> {
+        let mut state = serde_json::Map::new();
+        
+        for property in DIAGNOSTIC_PROPERTIES {
+            let value = match self.get_property(property) {
+                Ok(value) => value,
+                Err(Error::MpvError(e)) => {
+                    debug!("Diagnostic property {} unavailable: {}", property, e);
+                    Value::Null
+                },
+                Err(e) => return Err(e),
+            };
+            state.insert(property.to_string(), value);
+        }
+        
+        Ok(Value::Object(state))
+    }
+    
     /// Takes a screenshot
     pub fn screenshot(&mut self, include_subtitles: bool) -> Result<Value> {
         let screenshot_type = if include_subtitles { "subtitles" } else { "video" };
         self.command("screenshot", &[json!(screenshot_type)])
     }
     
+    /// Takes a screenshot and saves it to `path`; the image format follows the file extension
+    pub fn screenshot_to_file(&mut self, path: &str, include_subtitles: bool) -> Result<Value> {
+        let screenshot_type = if include_subtitles { "subtitles" } else { "video" };
+        self.command("screenshot-to-file", &[json!(path), json!(screenshot_type)])
+    }
+    
+    /// Advances exactly one frame, pausing playback (mpv pauses implicitly)
+    pub fn frame_step(&mut self) -> Result<Value> {
+        self.command("frame-step", &[])
+    }
+    
+    /// Goes back exactly one frame, pausing playback (mpv pauses implicitly)
+    pub fn frame_back_step(&mut self) -> Result<Value> {
+        self.command("frame-back-step", &[])
+    }
+    
     /// Quits mpv
     pub fn quit(&mut self) -> Result<Value> {
         let result = self.command("quit", &[]);
@@ -986,18 +2037,53 @@ impl MpvIpcClient {
     }
     
     /// Gets the current playback status (playing, paused, idle)
+    /// Buffering is reported as "playing", as it was before `playback_state` existed
     pub fn get_playback_status(&mut self) -> Result<String> {
+        let status = match self.playback_state()? {
+            PlaybackState::Buffering => PlaybackState::Playing,
+            state => state,
+        };
+        Ok(status.as_str().to_string())
+    }
+    
+    /// Gets the demuxer cache statistics, reading unavailable properties as 0
+    pub fn get_cache_stats(&mut self) -> Result<CacheStats> {
+        let values = self.get_properties(&["cache-buffering-state", "demuxer-cache-duration", "demuxer-cache-time"])?;
+        
+        // Without a cache (e.g. local files) these are unavailable, which reads as 0
+        let number = |name: &str| values.get(name).and_then(|v| v.as_f64()).unwrap_or(0.0);
+        
+        Ok(CacheStats {
+            buffering_percent: number("cache-buffering-state"),
+            cache_duration_secs: number("demuxer-cache-duration"),
+            cache_time_secs: number("demuxer-cache-time"),
+        })
+    }
+    
+    /// Gets the current playback state
+    pub fn playback_state(&mut self) -> Result<PlaybackState> {
         // First check if we're paused
-        match self.get_pause()? {
-            true => return Ok("paused".to_string()),
-            false => {
-                // Check if we're idle or playing
-                match self.get_property("idle-active")? {
-                    Value::Bool(true) => Ok("idle".to_string()),
-                    Value::Bool(false) => Ok("playing".to_string()),
-                    _ => Err(Error::MpvError("Invalid idle-active type".to_string()))
-                }
-            }
+        if self.get_pause()? {
+            return Ok(PlaybackState::Paused);
+        }
+        
+        // Check if we're idle
+        match self.get_property("idle-active")? {
+            Value::Bool(true) => return Ok(PlaybackState::Idle),
+            Value::Bool(false) => {},
+            _ => return Err(Error::MpvError("Invalid idle-active type".to_string()))
+        }
+        
+        // Playing, unless stalled on the cache (the property is unavailable without a cache)
+        let buffering = self.get_property("paused-for-cache")
+            .ok()
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false);
+        
+        if buffering {
+            Ok(PlaybackState::Buffering)
+        } else {
+            Ok(PlaybackState::Playing)
         }
     }
     
@@ -1013,8 +2099,515 @@ impl MpvIpcClient {
         self.config.poll_interval_ms
     }
     
+    /// Replaces the IPC configuration without dropping the connection.
+    /// The new timeouts and reconnection settings apply from the next request.
+    pub fn update_config(&mut self, config: IpcConfig) {
+        debug!("Updating IPC config for {}: {:?}", self.socket_path, config);
+        self.config = config;
+        self.reset_reconnect_attempts();
+    }
+    
     /// Returns whether the client has been intentionally closed
     pub fn is_intentionally_closed(&self) -> bool {
         self.intentionally_closed
     }
-} 
\ No newline at end of file
+}
+
+/// Converts a delay in milliseconds to mpv's native seconds
+pub fn ms_to_seconds(ms: i64) -> f64 {
+    ms as f64 / 1000.0
+}
+
+/// Converts a delay in mpv's native seconds to whole milliseconds, rounding to the nearest value
+pub fn seconds_to_ms(seconds: f64) -> i64 {
+    (seconds * 1000.0).round() as i64
+}
+
+/// Parses an mpv string-list property value into its entries
+pub fn parse_string_list(value: &Value) -> Option<Vec<String>> {
+    match value {
+        Value::Array(items) => Some(
+            items.iter()
+                .filter_map(|item| item.as_str().map(|s| s.to_string()))
+                .collect()
+        ),
+        _ => None,
+    }
+}
+
+/// Parses an `aid`/`sid`/`vid` property value.
+/// `"no"`, `"auto"` (nothing selected yet) and `false` map to `Some(None)`;
+/// unrecognized values return `None`.
+pub fn parse_track_id(value: &Value) -> Option<Option<i64>> {
+    match value {
+        Value::Number(n) => n.as_i64().map(Some),
+        Value::Bool(false) => Some(None),
+        Value::String(s) if s == "no" || s == "auto" => Some(None),
+        Value::String(s) => s.parse::<i64>().ok().map(Some),
+        _ => None,
+    }
+}
+
+/// Parses a `HH:MM:SS(.mmm)`, `MM:SS(.mmm)` or `SS(.mmm)` timecode into seconds
+pub fn parse_timecode(timecode: &str) -> Result<f64> {
+    let invalid = |reason: &str| Error::MpvError(format!("Invalid timecode '{}': {}", timecode, reason));
+    
+    let parts: Vec<&str> = timecode.trim().split(':').collect();
+    if parts.len() > 3 {
+        return Err(invalid("expected at most HH:MM:SS"));
+    }
+    
+    let (seconds_part, whole_parts) = parts.split_last().unwrap();
+    let seconds: f64 = seconds_part.parse()
+        .map_err(|_| invalid("seconds must be a number"))?;
+    if !seconds.is_finite() || seconds < 0.0 {
+        return Err(invalid("seconds must be a non-negative number"));
+    }
+    if !whole_parts.is_empty() && seconds >= 60.0 {
+        return Err(invalid("seconds must be below 60"));
+    }
+    
+    let mut total = 0.0;
+    for (index, part) in whole_parts.iter().enumerate() {
+        let value: u64 = part.parse()
+            .map_err(|_| invalid("hours and minutes must be whole numbers"))?;
+        // Minutes are bounded when hours are given
+        if index > 0 && value >= 60 {
+            return Err(invalid("minutes must be below 60"));
+        }
+        total = total * 60.0 + value as f64;
+    }
+    
+    Ok(total * 60.0 + seconds)
+}
+
+/// Extracts the names of non-built-in profiles from a `profile-list` value
+pub fn parse_profile_names(profiles: &[Value]) -> Vec<String> {
+    profiles.iter()
+        .filter_map(|profile| profile.get("name").and_then(Value::as_str))
+        .filter(|name| !BUILTIN_PROFILES.contains(name))
+        .map(|name| name.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    
+    #[test]
+    fn chapter_info_parses_title_and_time() {
+        let chapter = ChapterInfo::from_value(&json!({"title": "Opening", "time": 12.5})).unwrap();
+        assert_eq!(chapter.title.as_deref(), Some("Opening"));
+        assert_eq!(chapter.time, 12.5);
+        
+        let untitled = ChapterInfo::from_value(&json!({"time": 90.0})).unwrap();
+        assert_eq!(untitled.title, None);
+        
+        assert!(ChapterInfo::from_value(&json!({"title": "No time"})).is_none());
+    }
+    
+    #[test]
+    fn delay_ms_round_trips_through_seconds() {
+        for ms in [0, 1, 250, -250, -1500, 123_456] {
+            assert_eq!(seconds_to_ms(ms_to_seconds(ms)), ms);
+        }
+        assert_eq!(ms_to_seconds(-250), -0.25);
+        assert_eq!(seconds_to_ms(-0.1234), -123);
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn delay_ms_accessors_use_seconds_on_the_wire() {
+        let mock = crate::core::player::mock::MockMpv::start();
+        let mut client = mock.client();
+        
+        client.set_sub_delay_ms(-250).unwrap();
+        client.set_audio_delay_ms(1500).unwrap();
+        
+        assert_eq!(mock.property("sub-delay"), Some(json!(-0.25)));
+        assert_eq!(mock.property("audio-delay"), Some(json!(1.5)));
+        assert_eq!(client.get_sub_delay_ms().unwrap(), -250);
+        assert_eq!(client.get_audio_delay_ms().unwrap(), 1500);
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn pitch_correction_toggles_at_runtime() {
+        let mock = crate::core::player::mock::MockMpv::start();
+        let mut client = mock.client();
+        
+        client.set_pitch_correction(false).unwrap();
+        assert_eq!(mock.property("audio-pitch-correction"), Some(json!(false)));
+        
+        client.set_pitch_correction(true).unwrap();
+        assert_eq!(mock.property("audio-pitch-correction"), Some(json!(true)));
+    }
+    
+    #[test]
+    fn parses_a_protocol_list() {
+        let value = json!(["appending", "file", "http", "https", "rtmp", "rtsp", "bd", "dvd"]);
+        let protocols = parse_string_list(&value).unwrap();
+        
+        assert_eq!(protocols.len(), 8);
+        assert!(protocols.contains(&"rtsp".to_string()));
+        assert!(parse_string_list(&json!("http")).is_none());
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn supports_protocol_ignores_case_and_separator() {
+        let mock = crate::core::player::mock::MockMpv::start();
+        mock.set("protocol-list", json!(["file", "http", "https", "rtsp"]));
+        let mut client = mock.client();
+        
+        assert!(client.supports_protocol("RTSP://").unwrap());
+        assert!(client.supports_protocol("https").unwrap());
+        assert!(!client.supports_protocol("srt").unwrap());
+    }
+    
+    #[test]
+    fn parses_release_and_git_versions() {
+        let release = MpvVersion::parse("mpv 0.38.0").unwrap();
+        assert_eq!(release, MpvVersion { major: 0, minor: 38, patch: 0, git_hash: None });
+        
+        let git = MpvVersion::parse("mpv git-abc1234").unwrap();
+        assert_eq!((git.major, git.minor, git.patch), (0, 0, 0));
+        assert_eq!(git.git_hash.as_deref(), Some("abc1234"));
+        assert!(git.at_least(0, 38, 0));
+        
+        let described = MpvVersion::parse("mpv v0.38.0-12-gabc1234 Copyright © 2000-2024 mpv/MPlayer/mplayer2 projects").unwrap();
+        assert_eq!((described.minor, described.git_hash.as_deref()), (38, Some("abc1234")));
+        
+        assert!(release.at_least(0, 37, 1));
+        assert!(!release.at_least(0, 39, 0));
+        assert!(MpvVersion::parse("mpv").is_err());
+        assert!(MpvVersion::parse("mpv x.y").is_err());
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn set_osd_level_writes_the_property() {
+        let mock = crate::core::player::mock::MockMpv::start();
+        let mut client = mock.client();
+        
+        client.set_osd_level(3).unwrap();
+        assert_eq!(mock.property("osd-level"), Some(json!(3)));
+    }
+    
+    #[test]
+    fn parses_no_auto_and_numeric_track_ids() {
+        assert_eq!(parse_track_id(&json!(2)), Some(Some(2)));
+        assert_eq!(parse_track_id(&json!("3")), Some(Some(3)));
+        assert_eq!(parse_track_id(&json!("no")), Some(None));
+        assert_eq!(parse_track_id(&json!(false)), Some(None));
+        assert_eq!(parse_track_id(&json!("auto")), Some(None));
+        assert_eq!(parse_track_id(&json!("jpn")), None);
+        assert_eq!(parse_track_id(&json!(null)), None);
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn current_tracks_are_read_from_aid_and_sid() {
+        let mock = crate::core::player::mock::MockMpv::start();
+        mock.set("aid", json!(1));
+        mock.set("sid", json!(false));
+        let mut client = mock.client();
+        
+        assert_eq!(client.get_current_audio_track().unwrap(), Some(1));
+        assert_eq!(client.get_current_subtitle_track().unwrap(), None);
+        
+        mock.set("sid", json!({"unexpected": true}));
+        assert!(client.get_current_subtitle_track().is_err());
+    }
+    
+    #[test]
+    fn parses_each_timecode_form() {
+        assert_eq!(parse_timecode("1:23:45").unwrap(), 5025.0);
+        assert_eq!(parse_timecode("01:02:03.500").unwrap(), 3723.5);
+        assert_eq!(parse_timecode("12:34").unwrap(), 754.0);
+        assert_eq!(parse_timecode("90:00").unwrap(), 5400.0);
+        assert_eq!(parse_timecode("42.25").unwrap(), 42.25);
+        assert_eq!(parse_timecode(" 75 ").unwrap(), 75.0);
+    }
+    
+    #[test]
+    fn rejects_malformed_timecodes() {
+        for timecode in ["", "abc", "1:2:3:4", "1:60:00", "1:00:60", "12:75", "-5", "1.5:00", "1::00", "inf"] {
+            assert!(
+                matches!(parse_timecode(timecode), Err(Error::MpvError(message)) if message.contains("Invalid timecode")),
+                "{:?} should be rejected", timecode
+            );
+        }
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn seek_timecode_seeks_to_the_parsed_position() {
+        let mock = crate::core::player::mock::MockMpv::start();
+        let mut client = mock.client();
+        
+        client.seek_timecode("1:02:03").unwrap();
+        assert_eq!(mock.property("time-pos"), Some(json!(3723.0)));
+        assert!(client.seek_timecode("1:99").is_err());
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn dump_state_includes_every_diagnostic_key() {
+        let mock = crate::core::player::mock::MockMpv::start();
+        mock.set("hwdec-current", json!("vaapi"));
+        mock.set("video-params", json!({"w": 1920, "h": 1080, "pixelformat": "nv12"}));
+        mock.set("avsync", json!(0.002));
+        let mut client = mock.client();
+        
+        let state = client.dump_state().unwrap();
+        let state = state.as_object().unwrap();
+        
+        assert_eq!(state.len(), DIAGNOSTIC_PROPERTIES.len());
+        assert!(DIAGNOSTIC_PROPERTIES.iter().all(|property| state.contains_key(*property)));
+        assert_eq!(state["hwdec-current"], json!("vaapi"));
+        assert_eq!(state["video-params"]["w"], json!(1920));
+        assert_eq!(state["volume"], json!(100.0));
+        // Properties mpv cannot report are kept as null rather than failing the dump
+        assert_eq!(state["vo-passes"], Value::Null);
+    }
+    
+    #[test]
+    fn profile_names_skip_builtin_profiles() {
+        let profiles = json!([
+            {"name": "default", "options": []},
+            {"name": "gpu-hq", "options": []},
+            {"name": "anime", "profile-desc": "Line art", "options": [{"key": "deband", "value": "yes"}]},
+            {"name": "low-latency", "options": []},
+            {"name": "night"},
+            {"options": []},
+        ]);
+        
+        assert_eq!(parse_profile_names(profiles.as_array().unwrap()), ["anime", "night"]);
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn get_profiles_rejects_a_non_list_value() {
+        let mock = crate::core::player::mock::MockMpv::start();
+        mock.set("profile-list", json!([{"name": "pseudo-gui"}, {"name": "cinema"}]));
+        let mut client = mock.client();
+        
+        assert_eq!(client.get_profiles().unwrap(), ["cinema"]);
+        
+        mock.set("profile-list", json!("cinema"));
+        assert!(client.get_profiles().is_err());
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn set_hr_seek_writes_the_property() {
+        let mock = crate::core::player::mock::MockMpv::start();
+        let mut client = mock.client();
+        
+        client.set_hr_seek(HrSeek::Yes).unwrap();
+        assert_eq!(mock.property("hr-seek"), Some(json!("yes")));
+        client.set_hr_seek(HrSeek::Default).unwrap();
+        assert_eq!(mock.property("hr-seek"), Some(json!("default")));
+    }
+    
+    #[test]
+    fn chapter_loop_bounds_run_to_the_next_chapter_or_the_end() {
+        let list = json!([
+            {"title": "Intro", "time": 0.0},
+            {"title": "Verse", "time": 30.5},
+            {"title": "Chorus", "time": 75.0},
+        ]);
+        let chapters: Vec<ChapterInfo> = list.as_array().unwrap().iter().filter_map(ChapterInfo::from_value).collect();
+        
+        assert_eq!(chapter_loop_bounds(&chapters, 0, 120.0), Some((0.0, 30.5)));
+        assert_eq!(chapter_loop_bounds(&chapters, 1, 120.0), Some((30.5, 75.0)));
+        assert_eq!(chapter_loop_bounds(&chapters, 2, 120.0), Some((75.0, 120.0)));
+        assert_eq!(chapter_loop_bounds(&chapters, 3, 120.0), None);
+        // A last chapter starting at or after the reported duration has nothing to loop
+        assert_eq!(chapter_loop_bounds(&chapters, 2, 75.0), None);
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn set_deband_writes_all_five_properties() {
+        let mock = crate::core::player::mock::MockMpv::start();
+        let mut client = mock.client();
+        
+        client.set_deband(DebandParams { enabled: true, iterations: 4, threshold: 48, range: 24, grain: 32 }).unwrap();
+        
+        assert_eq!(mock.property("deband"), Some(json!(true)));
+        assert_eq!(mock.property("deband-iterations"), Some(json!(4)));
+        assert_eq!(mock.property("deband-threshold"), Some(json!(48)));
+        assert_eq!(mock.property("deband-range"), Some(json!(24)));
+        assert_eq!(mock.property("deband-grain"), Some(json!(32)));
+    }
+    
+    #[test]
+    fn track_display_name_falls_back_to_language_codec_and_id() {
+        let track = |value: Value| Track::from_value(&value).unwrap();
+        
+        assert_eq!(track(json!({"id": 1, "type": "audio", "title": "Commentary", "lang": "eng", "codec": "aac"})).display_name(), "Commentary");
+        assert_eq!(track(json!({"id": 2, "type": "audio", "title": "", "lang": "jpn", "codec": "aac"})).display_name(), "Japanese");
+        assert_eq!(track(json!({"id": 3, "type": "sub", "lang": "xx", "codec": "ass"})).display_name(), "xx");
+        assert_eq!(track(json!({"id": 4, "type": "sub", "codec": "subrip"})).display_name(), "subrip");
+        assert_eq!(track(json!({"id": 5, "type": "video"})).display_name(), "Track 5");
+    }
+    
+    #[test]
+    fn language_codes_map_from_both_iso_forms() {
+        assert_eq!(language_name("en"), Some("English"));
+        assert_eq!(language_name("ENG"), Some("English"));
+        assert_eq!(language_name("fre"), language_name("fra"));
+        assert_eq!(language_name("de"), Some("German"));
+        assert_eq!(language_name("zz"), None);
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn transform_round_trips_through_the_struct() {
+        let mock = crate::core::player::mock::MockMpv::start();
+        let mut client = mock.client();
+        let transform = VideoTransform { zoom: 1.5, pan_x: -0.25, pan_y: 0.125 };
+        
+        client.set_transform(transform).unwrap();
+        
+        assert_eq!(mock.property("video-zoom"), Some(json!(1.5)));
+        assert_eq!(mock.property("video-pan-x"), Some(json!(-0.25)));
+        assert_eq!(mock.property("video-pan-y"), Some(json!(0.125)));
+        assert_eq!(client.get_transform().unwrap(), transform);
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn partial_write_reconnects_instead_of_sending_corrupt_data() {
+        use std::io::{BufRead, BufReader};
+        use std::os::unix::net::UnixListener;
+        
+        let dir = std::env::temp_dir().join(format!("playa-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let socket_path = dir.join("mpv.sock").display().to_string();
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        
+        let server = std::thread::spawn(move || {
+            // The first connection fails partway through the request
+            let (mut first, _) = listener.accept().unwrap();
+            let mut partial = [0u8; 1000];
+            first.read_exact(&mut partial).unwrap();
+            drop(first);
+            
+            // The reconnected one answers every request and keeps the lines it got
+            let (second, _) = listener.accept().unwrap();
+            let mut writer = second.try_clone().unwrap();
+            let mut lines = Vec::new();
+            for line in BufReader::new(second).lines().map_while(|line| line.ok()).take(2) {
+                let request: Value = serde_json::from_str(&line).unwrap_or(Value::Null);
+                let response = json!({"error": "success", "data": false, "request_id": request["request_id"]});
+                writeln!(writer, "{}", response).unwrap();
+                lines.push(line);
+            }
+            lines
+        });
+        
+        let mut client = MpvIpcClient::connect_with_config(&socket_path, IpcConfig::new(1000, 50, true, 3, 50)).unwrap();
+        // Larger than the socket buffer, so the write is cut off while in progress
+        let text = "x".repeat(4 * 1024 * 1024);
+        
+        client.command("show-text", &[json!(text)]).unwrap();
+        assert_eq!(client.get_property("pause").unwrap(), json!(false));
+        assert_eq!(client.reconnections(), 1);
+        
+        let lines = server.join().unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(lines.len(), 2);
+        for line in &lines {
+            let request: Value = serde_json::from_str(line).expect("request was corrupted");
+            assert!(request["command"].is_array());
+        }
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn stream_record_starts_and_stops_at_runtime() {
+        let mock = crate::core::player::mock::MockMpv::start();
+        let mut client = mock.client();
+        
+        client.set_stream_record(Some(Path::new("/tmp/stream-dump.ts"))).unwrap();
+        assert_eq!(mock.property("stream-record"), Some(json!("/tmp/stream-dump.ts")));
+        
+        client.set_stream_record(None).unwrap();
+        assert_eq!(mock.property("stream-record"), Some(json!("")));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn avsync_is_read_from_mpv() {
+        let mock = crate::core::player::mock::MockMpv::start();
+        mock.set("avsync", json!(-0.042));
+        let mut client = mock.client();
+        
+        assert_eq!(client.get_avsync().unwrap(), -0.042);
+        assert_eq!(client.dump_state().unwrap()["avsync"], json!(-0.042));
+        
+        mock.set("avsync", json!("n/a"));
+        assert!(client.get_avsync().is_err());
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn updated_timeout_is_honored_on_the_next_command() {
+        use std::os::unix::net::UnixListener;
+        
+        let dir = std::env::temp_dir().join(format!("playa-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let socket_path = dir.join("mpv.sock").display().to_string();
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        
+        let mut client = MpvIpcClient::connect_with_config(&socket_path, IpcConfig::new(10000, 50, false, 3, 50)).unwrap();
+        // Accept the connection but never answer, so every command runs into the timeout
+        let (_server, _) = listener.accept().unwrap();
+        
+        client.update_config(IpcConfig::new(200, 50, false, 3, 50));
+        assert!(client.is_connected());
+        
+        let started = Instant::now();
+        let result = client.get_property("pause");
+        let elapsed = started.elapsed();
+        let _ = std::fs::remove_dir_all(&dir);
+        
+        assert!(result.is_err());
+        assert!(elapsed >= Duration::from_millis(200) && elapsed < Duration::from_secs(2), "took {:?}", elapsed);
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn audio_channel_layouts_round_trip_through_mpv() {
+        let mock = crate::core::player::mock::MockMpv::start();
+        let mut client = mock.client();
+        
+        for (layout, value) in [
+            (AudioChannels::Auto, "auto"),
+            (AudioChannels::AutoSafe, "auto-safe"),
+            (AudioChannels::Stereo, "stereo"),
+            (AudioChannels::Mono, "mono"),
+            (AudioChannels::Custom("5.1,stereo".to_string()), "5.1,stereo"),
+        ] {
+            client.set_audio_channels(layout.clone()).unwrap();
+            assert_eq!(mock.property("audio-channels"), Some(json!(value)));
+            assert_eq!(client.get_audio_channels().unwrap(), layout);
+        }
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn frame_steps_send_the_exact_commands() {
+        let mock = crate::core::player::mock::MockMpv::start();
+        let mut client = mock.client();
+        
+        client.frame_step().unwrap();
+        client.frame_back_step().unwrap();
+        
+        assert_eq!(mock.commands(), vec![json!(["frame-step"]), json!(["frame-back-step"])]);
+    }
+}
@@ -0,0 +1,238 @@
+//! A fake mpv JSON IPC server for tests that need a running player.
+//! It answers property reads from a shared map, records every command it receives
+//! and can push events to its clients. Absolute seeks update `time-pos` and screenshots
+//! write an empty file, so callers can check their effects.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::core::config::ipc::IpcConfig;
+use crate::core::player::ipc::MpvIpcClient;
+
+/// Shared state of a mock server and its connection threads
+#[derive(Default)]
+struct MockState {
+    properties: Mutex<HashMap<String, Value>>,
+    failing: Mutex<HashMap<String, String>>,
+    failing_commands: Mutex<HashSet<String>>,
+    commands: Mutex<Vec<Value>>,
+    clients: Mutex<Vec<Arc<Mutex<UnixStream>>>>,
+    running: AtomicBool,
+}
+
+/// A mock mpv listening on a Unix socket in its own temporary directory
+pub(crate) struct MockMpv {
+    dir: PathBuf,
+    socket_path: String,
+    state: Arc<MockState>,
+}
+
+impl MockMpv {
+    /// Starts a server reporting a running, unpaused player
+    pub(crate) fn start() -> Self {
+        let dir = std::env::temp_dir().join(format!("playa-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let socket_path = dir.join("mpv.sock").display().to_string();
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        
+        let state = Arc::new(MockState::default());
+        state.running.store(true, Ordering::SeqCst);
+        {
+            let mut properties = state.properties.lock().unwrap();
+            for (name, value) in [
+                ("pid", json!(1000)),
+                ("pause", json!(false)),
+                ("idle-active", json!(false)),
+                ("eof-reached", json!(false)),
+                ("core-idle", json!(false)),
+                ("paused-for-cache", json!(false)),
+                ("volume", json!(100.0)),
+            ] {
+                properties.insert(name.to_string(), value);
+            }
+        }
+        
+        let accept_state = Arc::clone(&state);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if !accept_state.running.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(stream) = stream else { continue };
+                let writer = Arc::new(Mutex::new(stream.try_clone().unwrap()));
+                accept_state.clients.lock().unwrap().push(Arc::clone(&writer));
+                let state = Arc::clone(&accept_state);
+                thread::spawn(move || Self::serve(stream, writer, state));
+            }
+        });
+        
+        Self { dir, socket_path, state }
+    }
+    
+    /// Answers the requests of a single connection until it closes
+    fn serve(stream: UnixStream, writer: Arc<Mutex<UnixStream>>, state: Arc<MockState>) {
+        for line in BufReader::new(stream).lines().map_while(|line| line.ok()) {
+            let Ok(request) = serde_json::from_str::<Value>(&line) else { continue };
+            let command = request.get("command").cloned().unwrap_or(Value::Null);
+            state.commands.lock().unwrap().push(command.clone());
+            
+            let name = match &command {
+                Value::Array(args) => args.first().and_then(Value::as_str).unwrap_or_default().to_string(),
+                Value::Object(fields) => fields.get("name").and_then(Value::as_str).unwrap_or_default().to_string(),
+                _ => String::new(),
+            };
+            let arg = |index: usize| command.get(index).cloned().unwrap_or(Value::Null);
+            
+            let result = if state.failing_commands.lock().unwrap().contains(&name) {
+                Err("error running command".to_string())
+            } else {
+                match name.as_str() {
+                    "get_property" => {
+                        let property = arg(1).as_str().unwrap_or_default().to_string();
+                        match state.failing.lock().unwrap().get(&property) {
+                            Some(error) => Err(error.clone()),
+                            None => state.properties.lock().unwrap().get(&property).cloned()
+                                .ok_or_else(|| "property unavailable".to_string()),
+                        }
+                    },
+                    "set_property" => {
+                        let property = arg(1).as_str().unwrap_or_default().to_string();
+                        match state.failing.lock().unwrap().get(&property) {
+                            Some(error) => Err(error.clone()),
+                            None => {
+                                state.properties.lock().unwrap().insert(property, arg(2));
+                                Ok(Value::Null)
+                            },
+                        }
+                    },
+                    "seek" => {
+                        // Only absolute seeks move the reported position
+                        let flags = arg(2).as_str().unwrap_or_default().to_string();
+                        if let (Some(position), true) = (arg(1).as_f64(), flags.starts_with("absolute")) {
+                            state.properties.lock().unwrap().insert("time-pos".to_string(), json!(position));
+                        }
+                        Ok(Value::Null)
+                    },
+                    "screenshot-to-file" => {
+                        let path = arg(1).as_str().unwrap_or_default().to_string();
+                        std::fs::write(&path, b"").map(|_| Value::Null).map_err(|e| e.to_string())
+                    },
+                    _ => Ok(Value::Null),
+                }
+            };
+            
+            let mut response = match result {
+                Ok(data) => json!({"error": "success", "data": data}),
+                Err(error) => json!({"error": error}),
+            };
+            if let Some(request_id) = request.get("request_id") {
+                response["request_id"] = request_id.clone();
+            }
+            
+            let mut writer = writer.lock().unwrap();
+            if writer.write_all(format!("{}\n", response).as_bytes()).is_err() {
+                break;
+            }
+        }
+    }
+    
+    /// Path of the socket clients connect to
+    pub(crate) fn socket_path(&self) -> String {
+        self.socket_path.clone()
+    }
+    
+    /// Sets the value returned for a property
+    pub(crate) fn set(&self, name: &str, value: Value) {
+        self.state.properties.lock().unwrap().insert(name.to_string(), value);
+    }
+    
+    /// Returns the current value of a property, including ones written by clients
+    pub(crate) fn property(&self, name: &str) -> Option<Value> {
+        self.state.properties.lock().unwrap().get(name).cloned()
+    }
+    
+    /// Makes reads and writes of a property fail with `error`
+    pub(crate) fn fail_property(&self, name: &str, error: &str) {
+        self.state.failing.lock().unwrap().insert(name.to_string(), error.to_string());
+    }
+    
+    /// Makes every command with this name fail
+    pub(crate) fn fail_command(&self, name: &str) {
+        self.state.failing_commands.lock().unwrap().insert(name.to_string());
+    }
+    
+    /// Every command received so far, in order
+    pub(crate) fn commands(&self) -> Vec<Value> {
+        self.state.commands.lock().unwrap().clone()
+    }
+    
+    /// The commands received so far other than property reads and observers
+    pub(crate) fn actions(&self) -> Vec<Value> {
+        self.commands().into_iter()
+            .filter(|command| !matches!(
+                command.get(0).and_then(Value::as_str),
+                Some("get_property" | "observe_property" | "unobserve_property")
+            ))
+            .collect()
+    }
+    
+    /// Sends an event to every connected client
+    pub(crate) fn emit(&self, event: Value) {
+        for client in self.state.clients.lock().unwrap().iter() {
+            let _ = client.lock().unwrap().write_all(format!("{}\n", event).as_bytes());
+        }
+    }
+    
+    /// Closes every open connection, as a restarting mpv would
+    pub(crate) fn disconnect_all(&self) {
+        for client in self.state.clients.lock().unwrap().drain(..) {
+            let _ = client.lock().unwrap().shutdown(std::net::Shutdown::Both);
+        }
+    }
+    
+    /// Writes a stand-in mpv binary next to the socket that ignores its arguments and idles,
+    /// for tests that go through the real spawn path
+    pub(crate) fn fake_binary(&self) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        
+        let path = self.dir.join("mpv");
+        std::fs::write(&path, "#!/bin/sh\nexec sleep 30\n").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+    
+    /// Connects a client with short timeouts
+    pub(crate) fn client(&self) -> MpvIpcClient {
+        MpvIpcClient::connect_with_config(&self.socket_path, IpcConfig::new(1000, 50, true, 3, 50)).unwrap()
+    }
+}
+
+impl Drop for MockMpv {
+    fn drop(&mut self) {
+        self.state.running.store(false, Ordering::SeqCst);
+        self.disconnect_all();
+        // Wake the accept loop so it sees the server has stopped
+        let _ = UnixStream::connect(&self.socket_path);
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Polls `condition` until it holds or `timeout_ms` elapses, returning whether it held
+pub(crate) fn wait_until(timeout_ms: u64, mut condition: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+    while Instant::now() < deadline {
+        if condition() {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    condition()
+}
@@ -1,4 +1,7 @@
 // Re-exports from player submodules
 pub mod process;
 pub mod ipc;
-pub mod events; 
\ No newline at end of file
+pub mod events;
+pub mod playability;
+#[cfg(all(test, unix))]
+pub(crate) mod mock;
@@ -0,0 +1,193 @@
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+
+// Protocols reported by `mpv --list-protocols`, probed once per binary (None if the probe failed)
+static PROTOCOLS: OnceLock<Mutex<HashMap<PathBuf, Option<Vec<String>>>>> = OnceLock::new();
+
+/// File extensions mpv is known to play
+const MEDIA_EXTENSIONS: &[&str] = &[
+    "mkv", "mp4", "m4v", "mov", "avi", "webm", "wmv", "flv", "mpg", "mpeg", "m2ts", "mts", "ts",
+    "ogv", "3gp", "vob", "mp3", "flac", "m4a", "aac", "ogg", "opus", "wav", "wma", "m3u", "m3u8",
+    "pls", "iso",
+];
+
+/// Result of a cheap pre-check on whether a source can be played
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PlayabilityHint {
+    /// The source looks playable
+    Playable,
+    /// The local file does not exist
+    MissingFile,
+    /// mpv does not support the URL scheme
+    UnsupportedProtocol,
+    /// Could not tell without opening the source (e.g. an unusual extension)
+    Unknown,
+}
+
+/// Checks whether a path or URL is likely playable without spawning a player window,
+/// using mpv from PATH. See [`can_play_with`].
+pub fn can_play(source: &str) -> PlayabilityHint {
+    can_play_with(source, None)
+}
+
+/// Checks whether a path or URL is likely playable with the given mpv binary.
+/// Local files must exist and have a known media extension; URL schemes are checked
+/// against the binary's protocol list.
+pub fn can_play_with(source: &str, mpv_path: Option<&Path>) -> PlayabilityHint {
+    if let Some((scheme, rest)) = source.split_once("://") {
+        let scheme = scheme.to_lowercase();
+        if scheme == "file" {
+            return check_local_file(Path::new(rest));
+        }
+        
+        let mpv_binary = mpv_path.map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from("mpv"));
+        return check_protocol(&scheme, supported_protocols(&mpv_binary).as_ref());
+    }
+    
+    check_local_file(Path::new(source))
+}
+
+/// Checks a lowercase URL scheme against mpv's protocol list, if it could be probed
+fn check_protocol(scheme: &str, protocols: Option<&Vec<String>>) -> PlayabilityHint {
+    match protocols {
+        Some(protocols) if protocols.iter().any(|protocol| protocol == scheme) => PlayabilityHint::Playable,
+        Some(_) => PlayabilityHint::UnsupportedProtocol,
+        None => PlayabilityHint::Unknown,
+    }
+}
+
+/// Checks a local file's existence and extension
+fn check_local_file(path: &Path) -> PlayabilityHint {
+    if !path.exists() {
+        return PlayabilityHint::MissingFile;
+    }
+    
+    let known_extension = path.extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| MEDIA_EXTENSIONS.contains(&extension.to_lowercase().as_str()))
+        .unwrap_or(false);
+    
+    if known_extension {
+        PlayabilityHint::Playable
+    } else {
+        PlayabilityHint::Unknown
+    }
+}
+
+/// Returns the cached protocol list of a binary, probing it on first use
+fn supported_protocols(mpv_binary: &Path) -> Option<Vec<String>> {
+    let cache = PROTOCOLS.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(protocols) = cache.lock().unwrap().get(mpv_binary) {
+        return protocols.clone();
+    }
+    
+    let protocols = probe_protocols(mpv_binary);
+    cache.lock().unwrap().insert(mpv_binary.to_path_buf(), protocols.clone());
+    protocols
+}
+
+/// Runs `mpv --list-protocols`, returning `None` when mpv fails or lists nothing
+fn probe_protocols(mpv_binary: &Path) -> Option<Vec<String>> {
+    debug!("Probing mpv protocol list of {}", mpv_binary.display());
+    let output = match Command::new(mpv_binary).arg("--list-protocols").output() {
+        Ok(output) => output,
+        Err(e) => {
+            warn!("Failed to probe mpv protocols: {}", e);
+            return None;
+        }
+    };
+    
+    // An empty list would report every URL as unsupported, so treat it as unknown
+    let protocols = parse_protocol_list(&String::from_utf8_lossy(&output.stdout));
+    if !output.status.success() || protocols.is_empty() {
+        warn!("mpv protocol probe failed with {} and {} protocols", output.status, protocols.len());
+        return None;
+    }
+    
+    Some(protocols)
+}
+
+/// Parses the output of `mpv --list-protocols`
+pub fn parse_protocol_list(output: &str) -> Vec<String> {
+    output.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.ends_with(':'))
+        .map(|line| line.to_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+    
+    #[test]
+    fn missing_file_is_reported() {
+        assert_eq!(can_play("/nonexistent/movie.mkv"), PlayabilityHint::MissingFile);
+        assert_eq!(can_play("file:///nonexistent/movie.mkv"), PlayabilityHint::MissingFile);
+    }
+    
+    #[test]
+    fn local_mkv_is_playable() {
+        let path = std::env::temp_dir().join(format!("playa-{}.MKV", Uuid::new_v4()));
+        std::fs::write(&path, b"").unwrap();
+        let unknown = path.with_extension("xyz");
+        std::fs::write(&unknown, b"").unwrap();
+        
+        let mkv = can_play(&path.display().to_string());
+        let other = can_play(&unknown.display().to_string());
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&unknown);
+        
+        assert_eq!(mkv, PlayabilityHint::Playable);
+        assert_eq!(other, PlayabilityHint::Unknown);
+    }
+    
+    #[test]
+    fn unknown_scheme_is_unsupported() {
+        let protocols = parse_protocol_list("Protocols:\n\n  file\n  http\n  https\n  rtsp\n");
+        
+        assert_eq!(protocols, ["file", "http", "https", "rtsp"]);
+        assert_eq!(check_protocol("gopher", Some(&protocols)), PlayabilityHint::UnsupportedProtocol);
+        assert_eq!(check_protocol("https", Some(&protocols)), PlayabilityHint::Playable);
+        assert_eq!(check_protocol("https", None), PlayabilityHint::Unknown);
+    }
+    
+    /// Writes a shell script standing in for mpv
+    #[cfg(unix)]
+    fn fake_mpv(script: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        
+        let path = std::env::temp_dir().join(format!("playa-mpv-{}", Uuid::new_v4()));
+        std::fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn failed_or_empty_probe_is_unknown() {
+        let failing = fake_mpv("printf 'Protocols:\\n'\nexit 1");
+        let silent = fake_mpv("exit 0");
+        let listing = fake_mpv("printf 'Protocols:\\n  http\\n  https\\n'");
+        
+        let failing_hint = can_play_with("https://example.com/video.mkv", Some(&failing));
+        let silent_hint = can_play_with("https://example.com/video.mkv", Some(&silent));
+        let listing_hints = [
+            can_play_with("https://example.com/video.mkv", Some(&listing)),
+            can_play_with("rtsp://example.com/stream", Some(&listing)),
+        ];
+        for path in [failing, silent, listing] {
+            let _ = std::fs::remove_file(path);
+        }
+        
+        assert_eq!(failing_hint, PlayabilityHint::Unknown);
+        assert_eq!(silent_hint, PlayabilityHint::Unknown);
+        assert_eq!(listing_hints, [PlayabilityHint::Playable, PlayabilityHint::UnsupportedProtocol]);
+    }
+}
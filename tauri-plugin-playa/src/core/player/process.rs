@@ -1,15 +1,125 @@
-use crate::{Error, Result};
+use crate::{Error, Result, SpawnDiagnostics, SpawnStage};
 use log::{debug, error, info, warn};
+use std::collections::{HashSet, VecDeque};
 use std::fs;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read};
 use std::path::{Path, PathBuf};
-use std::process::{Child, Command};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use crate::core::player::events::MpvEventListener;
 use crate::core::plugin::WindowOptions;
 
+/// Controls whether mpv keeps the window open after playback ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum KeepOpen {
+    /// Close the file (and exit) when playback ends
+    #[default]
+    No,
+    /// Keep the last frame shown when the last file ends
+    Yes,
+    /// Keep the last frame shown after every file, even mid-playlist
+    Always,
+}
+
+impl KeepOpen {
+    /// Returns the value used for mpv's `--keep-open` option
+    pub fn as_mpv_value(&self) -> &'static str {
+        match self {
+            KeepOpen::No => "no",
+            KeepOpen::Yes => "yes",
+            KeepOpen::Always => "always",
+        }
+    }
+    
+    /// Parses the value mpv reports for its `keep-open` property
+    pub fn from_mpv_value(value: &serde_json::Value) -> Option<Self> {
+        match value {
+            serde_json::Value::Bool(false) => Some(KeepOpen::No),
+            serde_json::Value::Bool(true) => Some(KeepOpen::Yes),
+            serde_json::Value::String(value) => match value.as_str() {
+                "no" => Some(KeepOpen::No),
+                "yes" => Some(KeepOpen::Yes),
+                "always" => Some(KeepOpen::Always),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Default seek precision, mapped to mpv's `hr-seek` option
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HrSeek {
+    /// Precise seeks only where mpv considers them useful (e.g. relative seeks stay keyframe-based)
+    #[default]
+    Default,
+    /// Always seek precisely
+    Yes,
+    /// Seek precisely for absolute seeks only
+    Absolute,
+    /// Always seek to keyframes
+    No,
+}
+
+impl HrSeek {
+    /// Returns the value used for mpv's `hr-seek` option
+    pub fn as_mpv_value(&self) -> &'static str {
+        match self {
+            HrSeek::Default => "default",
+            HrSeek::Yes => "yes",
+            HrSeek::Absolute => "absolute",
+            HrSeek::No => "no",
+        }
+    }
+}
+
+/// Which on-screen controller mpv shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OscMode {
+    /// The bundled uosc, with mpv's own OSC and OSD bar turned off
+    #[default]
+    Bundled,
+    /// mpv's built-in OSC and OSD bar. With the bundled config, its scripts (uosc) are not loaded.
+    Native,
+    /// No controller at all. With the bundled config, its scripts (uosc) are not loaded.
+    None,
+}
+
+/// Selects a track to play when a file is loaded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrackSelector {
+    /// Select the track with this mpv track ID
+    Id(i64),
+    /// Select the first track matching this language code (e.g. `jpn` or `en`)
+    Lang(String),
+    /// Let mpv pick a track
+    Auto,
+    /// Disable the track type entirely
+    None,
+}
+
+impl TrackSelector {
+    /// Returns the mpv argument selecting this track, using the id option (`aid`/`sid`)
+    /// or the language option (`alang`/`slang`) as appropriate
+    pub fn to_mpv_arg(&self, id_option: &str, lang_option: &str) -> String {
+        match self {
+            TrackSelector::Id(id) => format!("--{}={}", id_option, id),
+            TrackSelector::Lang(lang) => format!("--{}={}", lang_option, lang),
+            TrackSelector::Auto => format!("--{}=auto", id_option),
+            TrackSelector::None => format!("--{}=no", id_option),
+        }
+    }
+}
+
 /// Options for spawning mpv
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct SpawnOptions {
     /// Preset to use (default, high-quality, low-latency, etc.)
     pub preset: Option<String>,
@@ -17,6 +127,94 @@ pub struct SpawnOptions {
     pub extra_args: Vec<String>,
     /// Window configuration options
     pub window: Option<WindowOptions>,
+    /// External chapters file (XML or ffmetadata) to load alongside the media
+    pub chapters_file: Option<PathBuf>,
+    /// Whether mpv keeps the window open after playback ends; unset leaves it to the mpv config
+    pub keep_open: Option<KeepOpen>,
+    /// Whether to preserve audio pitch when playback speed changes
+    pub pitch_correction: bool,
+    /// Skips validation of the bundled config files (also enabled by `NEATFLIX_SKIP_CONFIG_VALIDATION`)
+    pub skip_config_validation: bool,
+    /// Scale factor for OSD text, e.g. for readability on TVs
+    pub osd_scale: Option<f64>,
+    /// Explicit IPC socket path; a unique one is generated when unset
+    pub socket_path: Option<String>,
+    /// Audio track to select from the first frame
+    pub initial_audio: Option<TrackSelector>,
+    /// Subtitle track to select from the first frame
+    pub initial_subtitle: Option<TrackSelector>,
+    /// Default precision for every seek on the instance
+    pub hr_seek: HrSeek,
+    /// Trades smoothness for latency, e.g. for local screen mirroring
+    pub low_latency: bool,
+    /// Subtitle font that overrides the file's own styles
+    pub sub_font: Option<String>,
+    /// Subtitle font size (scaled to a 720p window height)
+    pub sub_font_size: Option<u32>,
+    /// Unix permission bits applied to the IPC socket once mpv creates it, e.g. `0o600`
+    pub socket_mode: Option<u32>,
+    /// mpv binary to launch instead of the one found on PATH
+    pub mpv_path: Option<PathBuf>,
+    /// File the played stream is recorded to as it is received
+    pub record_to: Option<PathBuf>,
+    /// File mpv writes its full log to, for diagnosing a single playback
+    pub log_file: Option<PathBuf>,
+    /// Tag embedded in the generated socket name (`mpv-socket-<tag>-<uuid>`)
+    pub instance_tag: Option<String>,
+    /// Environment variables set on the mpv process, e.g. `MPV_VERBOSE` or `LIBVA_DRIVER_NAME`.
+    /// These supplement the inherited environment rather than replace it;
+    /// a key that is already set is overridden.
+    pub env: Vec<(String, String)>,
+    /// Whether to capture mpv's stdout/stderr instead of inheriting them, see `OutputCapture`
+    pub capture_output: bool,
+    /// Directory the generated IPC socket is created in instead of `/tmp` (Unix only).
+    /// It is created if missing. Ignored when `socket_path` is set.
+    pub socket_dir: Option<PathBuf>,
+    /// mpv `--msg-level` value such as `all=warn` or `all=warn,vo=v`, `DEFAULT_LOG_LEVEL` (`all=v`) when unset
+    pub log_level: Option<String>,
+    /// Number of output lines kept when `capture_output` is set, `CAPTURED_OUTPUT_LINES` by default
+    pub output_max_lines: Option<usize>,
+    /// Whether mpv loads the crate's bundled config directory (uosc, fonts, mpv.conf).
+    /// When false, `--config-dir` is omitted and mpv loads the user's own config
+    /// (e.g. `~/.config/mpv`), which then also decides which scripts run.
+    pub use_bundled_config: bool,
+    /// Which on-screen controller to show. `Bundled` relies on uosc from the bundled config,
+    /// so without it no controller is shown unless the user's config provides one.
+    pub osc_mode: OscMode,
+}
+
+impl Default for SpawnOptions {
+    fn default() -> Self {
+        Self {
+            preset: None,
+            extra_args: Vec::new(),
+            window: None,
+            chapters_file: None,
+            keep_open: None,
+            pitch_correction: true,
+            skip_config_validation: false,
+            osd_scale: None,
+            socket_path: None,
+            initial_audio: None,
+            initial_subtitle: None,
+            hr_seek: HrSeek::Default,
+            low_latency: false,
+            sub_font: None,
+            sub_font_size: None,
+            socket_mode: None,
+            mpv_path: None,
+            record_to: None,
+            log_file: None,
+            instance_tag: None,
+            env: Vec::new(),
+            capture_output: false,
+            socket_dir: None,
+            log_level: None,
+            output_max_lines: None,
+            use_bundled_config: true,
+            osc_mode: OscMode::Bundled,
+        }
+    }
 }
 
 impl From<&crate::core::plugin::PlaybackOptions> for SpawnOptions {
@@ -33,17 +231,74 @@ impl From<&crate::core::plugin::PlaybackOptions> for SpawnOptions {
             extra_args.push(format!("--title={}", title));
         }
         
+        // Start minimized and paused so the stream can buffer before being shown
+        if options.prebuffer_secs.is_some() {
+            extra_args.push("--window-minimized=yes".to_string());
+            extra_args.push("--pause=yes".to_string());
+        }
+        
         Self {
             preset: options.preset.clone(),
             extra_args,
             window: options.window.clone(),
+            chapters_file: options.chapters_file.clone(),
+            keep_open: options.keep_open,
+            pitch_correction: options.pitch_correction,
+            osd_scale: options.osd_scale,
+            socket_path: options.socket_path.clone(),
+            initial_audio: options.initial_audio.clone(),
+            initial_subtitle: options.initial_subtitle.clone(),
+            hr_seek: options.hr_seek,
+            low_latency: options.low_latency,
+            sub_font: options.sub_font.clone(),
+            sub_font_size: options.sub_font_size,
+            socket_mode: options.socket_mode,
+            mpv_path: options.mpv_path.clone(),
+            record_to: options.record_to.clone(),
+            log_file: options.log_file.clone(),
+            instance_tag: options.instance_tag.clone(),
+            env: options.env.clone(),
+            capture_output: options.capture_output,
+            socket_dir: options.socket_dir.clone(),
+            log_level: options.log_level.clone(),
+            output_max_lines: options.output_max_lines,
+            use_bundled_config: options.use_bundled_config,
+            osc_mode: options.osc_mode,
+            ..Default::default()
         }
     }
 }
 
+/// Arguments applied for `SpawnOptions::low_latency`:
+/// mpv's low-latency profile, no frame timing, no cache and no interpolation
+pub const LOW_LATENCY_ARGS: &[&str] = &[
+    "--profile=low-latency",
+    "--untimed",
+    "--no-cache",
+    "--interpolation=no",
+];
+
+/// Environment variable that disables config file validation when set to `1`, `true` or `yes`
+pub const SKIP_CONFIG_VALIDATION_ENV: &str = "NEATFLIX_SKIP_CONFIG_VALIDATION";
+
+/// Returns whether config file validation should run for a spawn
+pub fn should_validate_config(options: &SpawnOptions) -> bool {
+    // Only the bundled config is checked
+    if options.skip_config_validation || !options.use_bundled_config {
+        return false;
+    }
+    
+    !skip_requested(std::env::var(SKIP_CONFIG_VALIDATION_ENV).ok().as_deref())
+}
+
+/// Returns whether a value of `SKIP_CONFIG_VALIDATION_ENV` asks to skip validation
+fn skip_requested(value: Option<&str>) -> bool {
+    value.is_some_and(|value| matches!(value.to_lowercase().as_str(), "1" | "true" | "yes"))
+}
+
 /// Validates configuration files to ensure they don't have common issues
-/// like trailing spaces after boolean values
-fn validate_config_files() -> Result<()> {
+/// like trailing spaces after boolean values. Problems found are added to `warnings`.
+fn validate_config_files(warnings: &mut Vec<String>) -> Result<()> {
     let script_opts_dir = {
         let mut path = crate::core::get_assets_path();
         path.push("script-opts");
@@ -52,6 +307,7 @@ fn validate_config_files() -> Result<()> {
     
     if !script_opts_dir.exists() {
         warn!("Script options directory not found at: {}", script_opts_dir.display());
+        warnings.push(format!("Script options directory not found at: {}", script_opts_dir.display()));
         return Ok(());
     }
     
@@ -69,14 +325,14 @@ fn validate_config_files() -> Result<()> {
         }
         
         debug!("Validating config file: {}", file_path.display());
-        validate_config_file(&file_path)?;
+        validate_config_file(&file_path, warnings)?;
     }
     
     Ok(())
 }
 
 /// Validates a single configuration file for common issues
-fn validate_config_file(file_path: &PathBuf) -> Result<()> {
+fn validate_config_file(file_path: &PathBuf, warnings: &mut Vec<String>) -> Result<()> {
     let file = fs::File::open(file_path)
         .map_err(|e| Error::ConfigError(format!("Failed to open config file {}: {}", file_path.display(), e)))?;
     
@@ -93,6 +349,7 @@ fn validate_config_file(file_path: &PathBuf) -> Result<()> {
             fixed_lines.push(fixed_line);
             needs_fixing = true;
             warn!("Fixed trailing space in boolean value in {}: '{}'", file_path.display(), line);
+            warnings.push(format!("Fixed trailing space in boolean value in {}: '{}'", file_path.display(), line));
         } else {
             fixed_lines.push(line);
         }
@@ -108,23 +365,114 @@ fn validate_config_file(file_path: &PathBuf) -> Result<()> {
     Ok(())
 }
 
+/// `--msg-level` used when `SpawnOptions::log_level` is unset: verbose logging to surface
+/// script loading errors
+pub const DEFAULT_LOG_LEVEL: &str = "all=v";
+
+/// Baseline arguments passed to every mpv instance.
+///
+/// - `--osc=no`, `--osd-bar=no`: the bundled uosc replaces mpv's standard OSC
+///   (left out for `OscMode::Native`)
+///
+/// The dynamic `--config-dir` and `--input-ipc-server` arguments are appended at spawn time,
+/// as is `--msg-level`, which is [`DEFAULT_LOG_LEVEL`] unless `SpawnOptions::log_level` is set.
+pub const DEFAULT_ARGS: &[&str] = &[
+    "--osc=no",
+    "--osd-bar=no",
+];
+
+/// Arguments for `OscMode::Native` on top of the bundled config, which turns the OSC off
+/// and loads uosc
+const NATIVE_OSC_BUNDLED_ARGS: &[&str] = &[
+    "--osc=yes",
+    "--osd-bar=yes",
+    "--load-scripts=no",
+];
+
+/// Returns the crate's default mpv arguments (see [`DEFAULT_ARGS`])
+pub fn default_mpv_args() -> Vec<String> {
+    DEFAULT_ARGS.iter().map(|s| s.to_string()).collect()
+}
+
 /// Generates a unique socket path for IPC communication.
 pub fn generate_socket_path() -> String {
+    generate_tagged_socket_path(None)
+}
+
+/// Generates a unique socket path, embedding `tag` in the name when given.
+/// Characters other than ASCII letters, digits, `-` and `_` are replaced with `_`.
+pub fn generate_tagged_socket_path(tag: Option<&str>) -> String {
+    let name = socket_name(tag);
+    
     #[cfg(target_family = "unix")]
     {
-        format!("/tmp/mpv-socket-{}", Uuid::new_v4())
+        format!("/tmp/{}", name)
     }
     
     #[cfg(target_family = "windows")]
     {
-        format!("\\\\.\\pipe\\mpv-socket-{}", Uuid::new_v4())
+        format!("\\\\.\\pipe\\{}", name)
     }
 }
 
+/// Generates a unique socket path inside `dir`, embedding `tag` in the name when given.
+/// Named pipes have no directory, so `dir` is ignored on Windows.
+pub fn generate_socket_path_in(dir: &Path, tag: Option<&str>) -> String {
+    #[cfg(target_family = "unix")]
+    {
+        dir.join(socket_name(tag)).display().to_string()
+    }
+    
+    #[cfg(target_family = "windows")]
+    {
+        let _ = dir;
+        generate_tagged_socket_path(tag)
+    }
+}
+
+/// Builds a unique socket file or pipe name
+fn socket_name(tag: Option<&str>) -> String {
+    match tag {
+        Some(tag) => {
+            let tag: String = tag.chars()
+                .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+                .collect();
+            format!("mpv-socket-{}-{}", tag, Uuid::new_v4())
+        }
+        None => format!("mpv-socket-{}", Uuid::new_v4()),
+    }
+}
+
+/// Longest Unix socket path accepted by every platform (`sun_path` is 104 bytes on macOS)
+#[cfg(target_family = "unix")]
+const MAX_SOCKET_PATH_LEN: usize = 103;
+
+/// Creates the socket directory if needed and checks that mpv can create its socket there
+#[cfg(target_family = "unix")]
+fn prepare_socket_dir(dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir)
+        .map_err(|e| Error::ConfigError(format!("Failed to create socket directory {}: {}", dir.display(), e)))?;
+    
+    let probe = dir.join(format!(".mpv-socket-probe-{}", Uuid::new_v4()));
+    fs::File::create(&probe)
+        .map_err(|e| Error::ConfigError(format!("Socket directory is not writable: {}: {}", dir.display(), e)))?;
+    let _ = fs::remove_file(&probe);
+    
+    Ok(())
+}
+
+/// Named pipes have no directory, so there is nothing to prepare on Windows
+#[cfg(not(target_family = "unix"))]
+fn prepare_socket_dir(_dir: &Path) -> Result<()> {
+    Ok(())
+}
+
 /// Applies window options to mpv command arguments
 fn apply_window_options(args: &mut Vec<String>, window: &WindowOptions) {
+    let borderless = window.borderless.unwrap_or(false);
+    
     // Apply borderless window mode
-    if window.borderless {
+    if borderless {
         args.push("--border=no".to_string());
         args.push("--no-window-decorations".to_string());
     }
@@ -148,7 +496,7 @@ fn apply_window_options(args: &mut Vec<String>, window: &WindowOptions) {
     }
     
     // Apply always on top
-    if window.always_on_top {
+    if window.always_on_top == Some(true) {
         args.push("--ontop".to_string());
     }
     
@@ -160,7 +508,7 @@ fn apply_window_options(args: &mut Vec<String>, window: &WindowOptions) {
     }
     
     // Apply hidden start
-    if window.start_hidden {
+    if window.start_hidden == Some(true) {
         args.push("--force-window=yes".to_string());
         args.push("--start-hidden".to_string());
     }
@@ -169,7 +517,7 @@ fn apply_window_options(args: &mut Vec<String>, window: &WindowOptions) {
     #[cfg(target_os = "windows")]
     {
         // On Windows, add extra options for proper borderless windows if needed
-        if window.borderless {
+        if borderless {
             args.push("--no-border".to_string());
         }
         
@@ -180,42 +528,119 @@ fn apply_window_options(args: &mut Vec<String>, window: &WindowOptions) {
     #[cfg(target_os = "linux")]
     {
         // On Linux, add X11-specific options if needed
-        if window.borderless {
+        if borderless {
             args.push("--x11-name=mpv-borderless".to_string());
         }
     }
 }
 
-/// Spawns mpv with the specified media file or URL and options.
-/// Returns the process handle and socket path for IPC communication.
-pub fn spawn_mpv(
-    file_or_url: &str, 
-    options: &SpawnOptions
-) -> Result<(Child, String)> {
-    info!("Launching mpv for media: {}", file_or_url);
+/// Pairs of mpv options that undo each other when both are given
+pub const CONFLICTING_OPTIONS: &[(&str, &str)] = &[
+    ("fullscreen", "geometry"),
+    ("fullscreen", "autofit"),
+    ("geometry", "autofit"),
+    ("window-minimized", "window-maximized"),
+];
+
+/// Returns the option name of an mpv argument, e.g. `volume` for `--volume=50`
+/// and `border` for `--no-border`
+fn arg_key(arg: &str) -> Option<&str> {
+    let option = arg.strip_prefix("--")?;
+    let key = option.split('=').next().unwrap_or(option);
+    Some(key.strip_prefix("no-").unwrap_or(key))
+}
+
+/// Returns whether an mpv argument turns its option on (i.e. is not `--no-x` or `--x=no`)
+fn arg_enabled(arg: &str) -> bool {
+    !arg.starts_with("--no-") && !arg.ends_with("=no")
+}
+
+/// Lists problems with `extra_args`: options given more than once (only the last one
+/// takes effect) and options that conflict with another extra or generated argument
+/// (see [`CONFLICTING_OPTIONS`]). These are warnings; mpv is still started.
+pub fn find_arg_conflicts(generated_args: &[String], extra_args: &[String]) -> Vec<String> {
+    let mut warnings = Vec::new();
     
-    // Validate configuration files before launching mpv
-    if let Err(e) = validate_config_files() {
-        warn!("Error validating config files: {}. Continuing anyway...", e);
+    let mut seen = HashSet::new();
+    let mut reported = HashSet::new();
+    for key in extra_args.iter().filter_map(|arg| arg_key(arg)) {
+        if !seen.insert(key) && reported.insert(key) {
+            warnings.push(format!("Option --{} is passed more than once in extra_args; only the last value takes effect", key));
+        }
     }
+    
+    let enabled_in = |args: &[String], key: &str| {
+        args.iter().any(|arg| arg_key(arg) == Some(key) && arg_enabled(arg))
+    };
+    for (first, second) in CONFLICTING_OPTIONS {
+        let first_extra = enabled_in(extra_args, first);
+        let second_extra = enabled_in(extra_args, second);
+        // Only report conflicts the caller introduced through extra_args
+        if !first_extra && !second_extra {
+            continue;
+        }
+        if (first_extra || enabled_in(generated_args, first)) && (second_extra || enabled_in(generated_args, second)) {
+            warnings.push(format!("Options --{} and --{} conflict; one of them will not take effect", first, second));
+        }
+    }
+    
+    warnings
+}
 
-    // Generate a unique socket path for IPC
-    let socket_path = generate_socket_path();
-    debug!("Generated IPC socket path: {}", socket_path);
-
-    // Build args using mpv's --option=value format
-    let mut args = Vec::<String>::new();
+/// Checks that a `--msg-level` value looks like mpv's `component=level[,component=level...]`
+fn validate_log_level(log_level: &str) -> Result<()> {
+    let valid = !log_level.is_empty()
+        && !log_level.contains(char::is_whitespace)
+        && log_level.split(',').all(|entry| {
+            matches!(entry.split_once('='), Some((component, level)) if !component.is_empty() && !level.is_empty())
+        });
     
-    // Add verbose flag to see script loading errors
-    args.push("--msg-level=all=v".to_string());
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::ConfigError(format!(
+            "Invalid log level '{}', expected component=level pairs such as all=warn", log_level
+        )))
+    }
+}
+
+/// Builds the full mpv argument list for a spawn.
+/// When `file_or_url` is `None`, mpv is started idle and waits for a `loadfile` command.
+pub fn build_mpv_args(
+    file_or_url: Option<&str>,
+    options: &SpawnOptions,
+    socket_path: &str,
+) -> Result<Vec<String>> {
+    build_playlist_args(file_or_url.as_slice(), options, socket_path)
+}
+
+/// Builds the mpv argument list for a spawn that queues every entry of `media` in order.
+/// When `media` is empty, mpv is started idle and waits for a `loadfile` command.
+pub fn build_playlist_args(
+    media: &[&str],
+    options: &SpawnOptions,
+    socket_path: &str,
+) -> Result<Vec<String>> {
+    // Build args using mpv's --option=value format, starting from the crate defaults
+    let mut args = default_mpv_args();
     
-    // Add configuration directory
-    let config_dir_path = get_mpv_config_path();
-    args.push(format!("--config-dir={}", config_dir_path.to_str().unwrap()));
+    // Let mpv's own OSC through when requested
+    if options.osc_mode == OscMode::Native {
+        args.retain(|arg| arg != "--osc=no" && arg != "--osd-bar=no");
+    }
     
-    // Ensure uosc is used instead of the standard OSC
-    args.push("--osc=no".to_string());
-    args.push("--osd-bar=no".to_string());
+    // Add configuration directory, unless mpv should load the user's own config
+    if options.use_bundled_config {
+        let config_dir_path = get_mpv_config_path();
+        args.push(format!("--config-dir={}", config_dir_path.to_str().unwrap()));
+        
+        // The bundled config disables mpv's OSC and loads uosc, so undo that for other modes
+        match options.osc_mode {
+            OscMode::Bundled => {}
+            OscMode::Native => args.extend(NATIVE_OSC_BUNDLED_ARGS.iter().map(|arg| arg.to_string())),
+            OscMode::None => args.push("--load-scripts=no".to_string()),
+        }
+    }
     
     // Enable the JSON IPC server
     args.push(format!("--input-ipc-server={}", socket_path));
@@ -241,30 +666,363 @@ pub fn spawn_mpv(
         args.push("--border=no".to_string());
     }
     
+    // Load external chapters if provided
+    if let Some(chapters_file) = &options.chapters_file {
+        if !chapters_file.exists() {
+            error!("Chapters file not found: {}", chapters_file.display());
+            return Err(Error::ConfigError(format!("Chapters file not found: {}", chapters_file.display())));
+        }
+        args.push(format!("--chapters-file={}", chapters_file.display()));
+    }
+    
+    // Apply keep-open behavior only when requested, so the config's own setting applies otherwise
+    if let Some(keep_open) = options.keep_open {
+        args.push(format!("--keep-open={}", keep_open.as_mpv_value()));
+    }
+    
+    // Apply pitch correction for non-1.0 playback speeds
+    args.push(format!("--audio-pitch-correction={}", if options.pitch_correction { "yes" } else { "no" }));
+    
+    if let Some(osd_scale) = options.osd_scale {
+        args.push(format!("--osd-scale={}", osd_scale));
+    }
+    
+    // Leave the bundled config's seek precision alone unless one was requested
+    if options.hr_seek != HrSeek::Default {
+        args.push(format!("--hr-seek={}", options.hr_seek.as_mpv_value()));
+    }
+    
+    if options.low_latency {
+        args.extend(LOW_LATENCY_ARGS.iter().map(|arg| arg.to_string()));
+    }
+    
+    // Force the subtitle font, so embedded ASS styles don't override it
+    if let Some(sub_font) = &options.sub_font {
+        args.push(format!("--sub-font={}", sub_font));
+        args.push("--sub-ass-override=force".to_string());
+    }
+    if let Some(sub_font_size) = options.sub_font_size {
+        args.push(format!("--sub-font-size={}", sub_font_size));
+    }
+    
+    if let Some(record_to) = &options.record_to {
+        args.push(format!("--stream-record={}", record_to.display()));
+    }
+    
+    if let Some(log_file) = &options.log_file {
+        // Opening the file up front catches a missing or read-only directory before mpv starts
+        if let Err(e) = fs::OpenOptions::new().create(true).append(true).open(log_file) {
+            error!("Log file is not writable: {}: {}", log_file.display(), e);
+            return Err(Error::ConfigError(format!("Log file is not writable: {}: {}", log_file.display(), e)));
+        }
+        args.push(format!("--log-file={}", log_file.display()));
+    }
+    
+    match &options.log_level {
+        Some(log_level) => {
+            validate_log_level(log_level)?;
+            args.push(format!("--msg-level={}", log_level));
+        }
+        None => args.push(format!("--msg-level={}", DEFAULT_LOG_LEVEL)),
+    }
+    
+    // Preselect tracks so the right ones play from the first frame
+    if let Some(audio) = &options.initial_audio {
+        args.push(audio.to_mpv_arg("aid", "alang"));
+    }
+    if let Some(subtitle) = &options.initial_subtitle {
+        args.push(subtitle.to_mpv_arg("sid", "slang"));
+    }
+    
+    // Point out extra arguments that are repeated or fight with the generated ones
+    for warning in find_arg_conflicts(&args, &options.extra_args) {
+        warn!("{}", warning);
+    }
+    
     // Add any extra arguments (these will override preset settings)
     args.extend(options.extra_args.iter().cloned());
     
-    // Add the file or URL as the last argument
-    args.push(file_or_url.to_string());
+    if media.is_empty() {
+        // Without media, keep mpv running idle until a file is loaded over IPC
+        args.push("--idle=yes".to_string());
+    } else {
+        // Add the files or URLs as the last arguments, mpv plays them as a playlist
+        args.extend(media.iter().map(|entry| entry.to_string()));
+    }
+    
+    Ok(args)
+}
 
-    debug!("MPV arguments: {:?}", args);
+/// Number of output lines kept by an `OutputCapture` unless `output_max_lines` is set
+pub const CAPTURED_OUTPUT_LINES: usize = 100;
 
-    // Spawn mpv asynchronously
-    match Command::new("mpv").args(&args).spawn() {
-        Ok(child) => {
-            debug!("MPV process spawned with PID: {:?}", child.id());
-            if !Path::new(&socket_path).exists() {
-                error!("Socket file not created: {}", socket_path);
-                return Err(Error::MpvError("Failed to create socket file".to_string()));
+/// How long a spawn with `capture_output` waits for mpv to either create its socket or exit
+const STARTUP_GRACE_MS: u64 = 500;
+
+/// The most recent stdout/stderr lines of an mpv process spawned with `capture_output`
+#[derive(Debug, Clone)]
+pub struct OutputCapture {
+    lines: Arc<Mutex<VecDeque<String>>>,
+    readers: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    max_lines: usize,
+}
+
+impl OutputCapture {
+    /// Starts reading the piped stdout and stderr of a process in background threads
+    fn start(process: &mut Child, max_lines: usize) -> Self {
+        let capture = Self {
+            lines: Arc::new(Mutex::new(VecDeque::with_capacity(max_lines))),
+            readers: Arc::new(Mutex::new(Vec::new())),
+            max_lines,
+        };
+        
+        if let Some(stdout) = process.stdout.take() {
+            capture.read_from(stdout);
+        }
+        if let Some(stderr) = process.stderr.take() {
+            capture.read_from(stderr);
+        }
+        
+        capture
+    }
+    
+    /// Buffers lines from a stream until it closes, keeping only the newest ones
+    fn read_from(&self, stream: impl Read + Send + 'static) {
+        let lines = Arc::clone(&self.lines);
+        let max_lines = self.max_lines;
+        let reader = thread::spawn(move || {
+            for line in BufReader::new(stream).lines().map_while(|line| line.ok()) {
+                let mut lines = lines.lock().unwrap();
+                while lines.len() >= max_lines.max(1) {
+                    lines.pop_front();
+                }
+                lines.push_back(line);
+            }
+        });
+        self.readers.lock().unwrap().push(reader);
+    }
+    
+    /// Waits for the streams to close, so the tail of an exited process is complete
+    fn finish(&self) {
+        let readers: Vec<_> = self.readers.lock().unwrap().drain(..).collect();
+        for reader in readers {
+            let _ = reader.join();
+        }
+    }
+    
+    /// Returns the captured lines, oldest first
+    pub fn tail(&self) -> Vec<String> {
+        self.lines.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+/// Waits until mpv creates its socket or the grace window ends, returning its exit status if it quit first
+fn wait_for_early_exit(process: &mut Child, socket_path: &str) -> Option<ExitStatus> {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_millis(STARTUP_GRACE_MS);
+    while std::time::Instant::now() < deadline {
+        if let Ok(Some(status)) = process.try_wait() {
+            return Some(status);
+        }
+        if Path::new(socket_path).exists() {
+            return None;
+        }
+        thread::sleep(std::time::Duration::from_millis(20));
+    }
+    None
+}
+
+/// Spawns mpv with the specified media file or URL and options.
+/// Returns the process handle and socket path for IPC communication.
+pub fn spawn_mpv(
+    file_or_url: &str, 
+    options: &SpawnOptions
+) -> Result<(Child, String)> {
+    info!("Launching mpv for media: {}", file_or_url);
+    launch_mpv(&[file_or_url], options).map(|(process, socket_path, _)| (process, socket_path))
+}
+
+/// Spawns mpv like `spawn_mpv`, also returning the output capture when `capture_output` is set
+pub fn spawn_mpv_with_output(
+    file_or_url: &str,
+    options: &SpawnOptions
+) -> Result<(Child, String, Option<OutputCapture>)> {
+    info!("Launching mpv for media: {}", file_or_url);
+    launch_mpv(&[file_or_url], options)
+}
+
+/// Spawns mpv with a playlist of files or URLs, starting at the first entry.
+/// Returns the process handle, socket path and output capture like `spawn_mpv_with_output`.
+pub fn spawn_mpv_playlist(
+    sources: &[String],
+    options: &SpawnOptions
+) -> Result<(Child, String, Option<OutputCapture>)> {
+    if sources.is_empty() {
+        return Err(Error::ConfigError("Playlist must contain at least one entry".to_string()));
+    }
+    
+    info!("Launching mpv for a playlist of {} entries", sources.len());
+    let media: Vec<&str> = sources.iter().map(String::as_str).collect();
+    launch_mpv(&media, options)
+}
+
+/// Spawns an idle mpv instance with no media loaded.
+/// Media can be loaded later with the `loadfile` IPC command.
+/// Returns the process handle and socket path for IPC communication.
+pub fn spawn_mpv_idle(options: &SpawnOptions) -> Result<(Child, String)> {
+    info!("Launching idle mpv instance");
+    launch_mpv(&[], options).map(|(process, socket_path, _)| (process, socket_path))
+}
+
+/// Validates the config, builds the arguments and starts the mpv process
+fn launch_mpv(media: &[&str], options: &SpawnOptions) -> Result<(Child, String, Option<OutputCapture>)> {
+    // Validate configuration files before launching mpv, unless disabled for trusted configs
+    let mut config_warnings = Vec::new();
+    if should_validate_config(options) {
+        if let Err(e) = validate_config_files(&mut config_warnings) {
+            warn!("Error validating config files: {}. Continuing anyway...", e);
+            config_warnings.push(e.to_string());
+        }
+    } else {
+        debug!("Skipping config file validation");
+    }
+
+    // Resolve the mpv binary, failing early when an explicit path is wrong
+    let mpv_binary = options.mpv_path.clone().unwrap_or_else(|| PathBuf::from("mpv"));
+    if options.mpv_path.is_some() && !mpv_binary.exists() {
+        return Err(spawn_failure(
+            &mpv_binary, &[], config_warnings, None, SpawnStage::ResolveBinary,
+            format!("mpv binary not found at {}", mpv_binary.display()),
+        ));
+    }
+    debug!("Using mpv binary: {}", mpv_binary.display());
+
+    // Use the requested socket path or generate a unique one for IPC
+    let socket_path = match (&options.socket_path, &options.socket_dir) {
+        (Some(socket_path), _) => socket_path.clone(),
+        (None, Some(socket_dir)) => {
+            prepare_socket_dir(socket_dir)?;
+            let socket_path = generate_socket_path_in(socket_dir, options.instance_tag.as_deref());
+            
+            // A deep directory can push the path past what a Unix socket address holds
+            #[cfg(target_family = "unix")]
+            if socket_path.len() > MAX_SOCKET_PATH_LEN {
+                return Err(Error::ConfigError(format!(
+                    "Socket path is longer than {} bytes: {}", MAX_SOCKET_PATH_LEN, socket_path
+                )));
             }
             
-            Ok((child, socket_path))
+            socket_path
+        }
+        (None, None) => generate_tagged_socket_path(options.instance_tag.as_deref()),
+    };
+    debug!("Using IPC socket path: {}", socket_path);
+
+    let args = build_playlist_args(media, options, &socket_path)
+        .map_err(|e| spawn_failure(&mpv_binary, &[], config_warnings.clone(), None, SpawnStage::BuildArgs, e))?;
+
+    debug!("MPV arguments: {:?}", args);
+
+    // Spawn mpv asynchronously
+    let mut command = Command::new(&mpv_binary);
+    command.args(&args).envs(options.env.iter().map(|(key, value)| (key, value)));
+    if options.capture_output {
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    }
+    let mut child = command.spawn()
+        .map_err(|e| spawn_failure(&mpv_binary, &args, config_warnings.clone(), None, SpawnStage::Launch, e))?;
+    debug!("MPV process spawned with PID: {:?}", child.id());
+    
+    // With captured output, give mpv a moment to fail so its error text can be reported
+    let output = options.capture_output.then(|| {
+        OutputCapture::start(&mut child, options.output_max_lines.unwrap_or(CAPTURED_OUTPUT_LINES))
+    });
+    if let Some(output) = &output {
+        if let Some(status) = wait_for_early_exit(&mut child, &socket_path) {
+            output.finish();
+            return Err(spawn_failure(
+                &mpv_binary, &args, config_warnings, Some(output), SpawnStage::Launch,
+                format!("mpv exited during startup with {}", status),
+            ));
         }
-        Err(e) => {
-            error!("Failed to start mpv process");
-            Err(Error::Io(e.to_string()))
+    }
+    
+    // mpv creates the socket shortly after starting, so give it time on every path
+    let socket_result = wait_for_socket(&mut child, &socket_path)
+        .and_then(|_| match options.socket_mode {
+            Some(mode) => restrict_socket_permissions(&socket_path, mode),
+            None => Ok(()),
+        });
+    
+    if let Err((stage, cause)) = socket_result {
+        // mpv is of no use without its IPC socket
+        let _ = child.kill();
+        let _ = child.wait();
+        if let Some(output) = &output {
+            output.finish();
         }
+        return Err(spawn_failure(&mpv_binary, &args, config_warnings, output.as_ref(), stage, cause));
     }
+    
+    Ok((child, socket_path, output))
+}
+
+/// Logs a spawn failure and wraps it with everything needed to reproduce it
+fn spawn_failure(
+    mpv_binary: &Path,
+    args: &[String],
+    config_warnings: Vec<String>,
+    output: Option<&OutputCapture>,
+    stage: SpawnStage,
+    cause: impl ToString,
+) -> Error {
+    let diagnostics = SpawnDiagnostics {
+        program: mpv_binary.display().to_string(),
+        args: args.to_vec(),
+        config_warnings,
+        output: output.map(OutputCapture::tail).unwrap_or_default(),
+        stage,
+        cause: cause.to_string(),
+    };
+    error!("Failed to spawn mpv while {}: {}", diagnostics.stage, diagnostics.cause);
+    for line in &diagnostics.output {
+        error!("mpv: {}", line);
+    }
+    Error::SpawnFailed(Box::new(diagnostics))
+}
+
+/// How long to wait for mpv to create its IPC socket
+const SOCKET_CREATE_TIMEOUT_MS: u64 = 5000;
+
+/// Waits for mpv to create the IPC socket, failing early if mpv exits first
+fn wait_for_socket(process: &mut Child, socket_path: &str) -> std::result::Result<(), (SpawnStage, String)> {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_millis(SOCKET_CREATE_TIMEOUT_MS);
+    while !Path::new(socket_path).exists() {
+        if let Ok(Some(status)) = process.try_wait() {
+            return Err((SpawnStage::Launch, format!("mpv exited during startup with {}", status)));
+        }
+        if std::time::Instant::now() >= deadline {
+            return Err((SpawnStage::SocketCreate, format!("Socket file not created: {}", socket_path)));
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    Ok(())
+}
+
+/// Applies the given permission bits to the IPC socket
+#[cfg(target_family = "unix")]
+fn restrict_socket_permissions(socket_path: &str, mode: u32) -> std::result::Result<(), (SpawnStage, String)> {
+    use std::os::unix::fs::PermissionsExt;
+    
+    fs::set_permissions(socket_path, fs::Permissions::from_mode(mode))
+        .map_err(|e| (SpawnStage::SocketPermissions, format!("Failed to set socket permissions: {}", e)))?;
+    debug!("Set IPC socket permissions to {:o}: {}", mode, socket_path);
+    Ok(())
+}
+
+/// Named pipes have no Unix permission bits, so this is a no-op on Windows
+#[cfg(not(target_family = "unix"))]
+fn restrict_socket_permissions(_socket_path: &str, _mode: u32) -> std::result::Result<(), (SpawnStage, String)> {
+    Ok(())
 }
 
 /// Spawns mpv with the specified media file or URL.
@@ -314,4 +1072,509 @@ pub fn monitor_process(process: &mut Child, event_listener: &mut MpvEventListene
     }
     
     Ok(exit_code)
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const SOCKET: &str = "/tmp/mpv-socket-test";
+    
+    fn args_for(options: &SpawnOptions) -> Vec<String> {
+        build_mpv_args(Some("video.mkv"), options, SOCKET).unwrap()
+    }
+    
+    #[test]
+    fn chapters_file_is_passed_to_mpv() {
+        let chapters_file = std::env::temp_dir().join(format!("playa-chapters-{}.xml", Uuid::new_v4()));
+        fs::write(&chapters_file, "<Chapters/>").unwrap();
+        
+        let args = args_for(&SpawnOptions {
+            chapters_file: Some(chapters_file.clone()),
+            ..Default::default()
+        });
+        let _ = fs::remove_file(&chapters_file);
+        
+        assert!(args.contains(&format!("--chapters-file={}", chapters_file.display())));
+    }
+    
+    #[test]
+    fn missing_chapters_file_is_rejected() {
+        let options = SpawnOptions {
+            chapters_file: Some(PathBuf::from("/nonexistent/chapters.xml")),
+            ..Default::default()
+        };
+        
+        let result = build_mpv_args(Some("video.mkv"), &options, SOCKET);
+        
+        assert!(matches!(result, Err(Error::ConfigError(message)) if message.contains("Chapters file not found")));
+    }
+    
+    #[test]
+    fn keep_open_is_left_to_the_config_unless_set() {
+        let args = args_for(&SpawnOptions::default());
+        assert!(!args.iter().any(|arg| arg.starts_with("--keep-open")));
+        
+        let args = args_for(&SpawnOptions {
+            keep_open: Some(KeepOpen::Always),
+            ..Default::default()
+        });
+        assert!(args.contains(&"--keep-open=always".to_string()));
+    }
+    
+    #[test]
+    fn keep_open_parses_mpv_values() {
+        assert_eq!(KeepOpen::from_mpv_value(&serde_json::json!("always")), Some(KeepOpen::Always));
+        assert_eq!(KeepOpen::from_mpv_value(&serde_json::json!(true)), Some(KeepOpen::Yes));
+        assert_eq!(KeepOpen::from_mpv_value(&serde_json::json!("no")), Some(KeepOpen::No));
+        assert_eq!(KeepOpen::from_mpv_value(&serde_json::json!(3)), None);
+    }
+    
+    #[test]
+    fn spawn_args_start_with_the_default_args() {
+        let options = SpawnOptions::default();
+        let args = args_for(&options);
+        
+        assert_eq!(default_mpv_args(), DEFAULT_ARGS.iter().map(|arg| arg.to_string()).collect::<Vec<_>>());
+        assert_eq!(&args[..DEFAULT_ARGS.len()], default_mpv_args().as_slice());
+        // launch_mpv builds its arguments through build_playlist_args
+        assert_eq!(args, build_playlist_args(&["video.mkv"], &options, SOCKET).unwrap());
+        assert!(args.contains(&format!("--input-ipc-server={}", SOCKET)));
+        assert_eq!(args.last().map(String::as_str), Some("video.mkv"));
+    }
+    
+    #[test]
+    fn pitch_correction_spawn_arg() {
+        assert!(args_for(&SpawnOptions::default()).contains(&"--audio-pitch-correction=yes".to_string()));
+        
+        let args = args_for(&SpawnOptions {
+            pitch_correction: false,
+            ..Default::default()
+        });
+        assert!(args.contains(&"--audio-pitch-correction=no".to_string()));
+    }
+    
+    #[test]
+    fn config_validation_can_be_skipped() {
+        assert!(!should_validate_config(&SpawnOptions {
+            skip_config_validation: true,
+            ..Default::default()
+        }));
+        assert!(!should_validate_config(&SpawnOptions {
+            use_bundled_config: false,
+            ..Default::default()
+        }));
+    }
+    
+    #[test]
+    fn skip_env_values_are_parsed() {
+        for value in ["1", "true", "TRUE", "yes", "Yes"] {
+            assert!(skip_requested(Some(value)), "{}", value);
+        }
+        for value in ["0", "false", "no", ""] {
+            assert!(!skip_requested(Some(value)), "{}", value);
+        }
+        assert!(!skip_requested(None));
+    }
+    
+    #[test]
+    fn osd_scale_is_passed_to_mpv() {
+        assert!(!args_for(&SpawnOptions::default()).iter().any(|arg| arg.starts_with("--osd-scale=")));
+        
+        let args = args_for(&SpawnOptions {
+            osd_scale: Some(1.5),
+            ..Default::default()
+        });
+        assert!(args.contains(&"--osd-scale=1.5".to_string()));
+    }
+    
+    #[test]
+    fn initial_tracks_are_selected_by_id_and_language() {
+        let args = args_for(&SpawnOptions {
+            initial_audio: Some(TrackSelector::Lang("jpn".to_string())),
+            initial_subtitle: Some(TrackSelector::Id(2)),
+            ..Default::default()
+        });
+        assert!(args.contains(&"--alang=jpn".to_string()));
+        assert!(args.contains(&"--sid=2".to_string()));
+        
+        let args = args_for(&SpawnOptions {
+            initial_audio: Some(TrackSelector::Id(3)),
+            initial_subtitle: Some(TrackSelector::Lang("en".to_string())),
+            ..Default::default()
+        });
+        assert!(args.contains(&"--aid=3".to_string()));
+        assert!(args.contains(&"--slang=en".to_string()));
+    }
+    
+    #[test]
+    fn auto_and_disabled_tracks_use_the_id_option() {
+        assert_eq!(TrackSelector::Auto.to_mpv_arg("aid", "alang"), "--aid=auto");
+        assert_eq!(TrackSelector::None.to_mpv_arg("sid", "slang"), "--sid=no");
+        assert!(!args_for(&SpawnOptions::default()).iter().any(|arg| arg.starts_with("--aid=") || arg.starts_with("--sid=")));
+    }
+    
+    #[test]
+    fn hr_seek_is_passed_unless_default() {
+        assert!(!args_for(&SpawnOptions::default()).iter().any(|arg| arg.starts_with("--hr-seek=")));
+        
+        for (mode, arg) in [(HrSeek::Yes, "--hr-seek=yes"), (HrSeek::Absolute, "--hr-seek=absolute"), (HrSeek::No, "--hr-seek=no")] {
+            let args = args_for(&SpawnOptions {
+                hr_seek: mode,
+                ..Default::default()
+            });
+            assert!(args.contains(&arg.to_string()));
+        }
+    }
+    
+    #[test]
+    fn low_latency_args_are_passed_when_requested() {
+        let default_args = args_for(&SpawnOptions::default());
+        assert!(!default_args.iter().any(|arg| arg == "--untimed"));
+        
+        let args = args_for(&SpawnOptions {
+            low_latency: true,
+            ..Default::default()
+        });
+        for arg in ["--profile=low-latency", "--untimed", "--no-cache", "--interpolation=no"] {
+            assert!(args.contains(&arg.to_string()), "missing {}", arg);
+        }
+    }
+    
+    #[test]
+    fn sub_font_forces_the_ass_override() {
+        let args = args_for(&SpawnOptions {
+            sub_font: Some("Atkinson Hyperlegible".to_string()),
+            sub_font_size: Some(52),
+            ..Default::default()
+        });
+        assert!(args.contains(&"--sub-font=Atkinson Hyperlegible".to_string()));
+        assert!(args.contains(&"--sub-font-size=52".to_string()));
+        assert!(args.contains(&"--sub-ass-override=force".to_string()));
+        
+        // A size alone keeps the embedded styles
+        let args = args_for(&SpawnOptions {
+            sub_font_size: Some(52),
+            ..Default::default()
+        });
+        assert!(args.contains(&"--sub-font-size=52".to_string()));
+        assert!(!args.iter().any(|arg| arg.starts_with("--sub-ass-override=")));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn socket_permissions_are_restricted_after_spawn() {
+        use std::os::unix::fs::PermissionsExt;
+        
+        let mock = crate::core::player::mock::MockMpv::start();
+        fs::set_permissions(mock.socket_path(), fs::Permissions::from_mode(0o777)).unwrap();
+        
+        let (mut child, socket_path) = spawn_mpv("video.mkv", &SpawnOptions {
+            mpv_path: Some(mock.fake_binary()),
+            socket_path: Some(mock.socket_path().to_string()),
+            socket_mode: Some(0o600),
+            skip_config_validation: true,
+            ..Default::default()
+        }).unwrap();
+        let _ = child.kill();
+        let _ = child.wait();
+        
+        let mode = fs::metadata(&socket_path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+    
+    #[test]
+    fn stream_record_arg_is_passed_to_mpv() {
+        let args = args_for(&SpawnOptions {
+            record_to: Some(PathBuf::from("/tmp/stream-dump.ts")),
+            ..Default::default()
+        });
+        assert!(args.contains(&"--stream-record=/tmp/stream-dump.ts".to_string()));
+        
+        let args = args_for(&SpawnOptions::default());
+        assert!(!args.iter().any(|arg| arg.starts_with("--stream-record")));
+    }
+    
+    #[test]
+    fn log_file_arg_is_passed_to_mpv() {
+        let log_file = std::env::temp_dir().join(format!("playa-log-{}.txt", Uuid::new_v4()));
+        
+        let args = args_for(&SpawnOptions {
+            log_file: Some(log_file.clone()),
+            ..Default::default()
+        });
+        let _ = fs::remove_file(&log_file);
+        
+        assert!(args.contains(&format!("--log-file={}", log_file.display())));
+    }
+    
+    #[test]
+    fn unwritable_log_file_is_rejected() {
+        let options = SpawnOptions {
+            log_file: Some(PathBuf::from("/nonexistent/playa/mpv.log")),
+            ..Default::default()
+        };
+        
+        let result = build_mpv_args(Some("video.mkv"), &options, SOCKET);
+        assert!(matches!(result, Err(Error::ConfigError(message)) if message.contains("not writable")));
+    }
+    
+    #[test]
+    fn duplicate_extra_args_are_reported_once() {
+        let extra_args = ["--volume=50", "--volume=80", "--no-border", "--border", "--volume=90"]
+            .map(String::from);
+        
+        let warnings = find_arg_conflicts(&[], &extra_args);
+        
+        assert_eq!(warnings.len(), 2, "{:?}", warnings);
+        assert!(warnings[0].contains("--volume"));
+        assert!(warnings[1].contains("--border"));
+    }
+    
+    #[test]
+    fn known_conflicts_are_reported_when_extra_args_introduce_them() {
+        let generated = ["--geometry=1280x720".to_string()];
+        
+        let warnings = find_arg_conflicts(&generated, &["--fullscreen".to_string()]);
+        assert_eq!(warnings, ["Options --fullscreen and --geometry conflict; one of them will not take effect"]);
+        
+        // A disabled option, or a conflict only between generated args, is not reported
+        assert!(find_arg_conflicts(&generated, &["--fullscreen=no".to_string()]).is_empty());
+        assert!(find_arg_conflicts(&["--fullscreen".to_string(), generated[0].clone()], &[]).is_empty());
+    }
+    
+    #[test]
+    fn instance_tag_is_embedded_in_the_socket_path() {
+        let socket_path = generate_tagged_socket_path(Some("my-app"));
+        assert!(socket_path.contains("mpv-socket-my-app-"), "{}", socket_path);
+        
+        let sanitized = generate_tagged_socket_path(Some("my app/1"));
+        assert!(sanitized.contains("mpv-socket-my_app_1-"), "{}", sanitized);
+        
+        assert_ne!(generate_tagged_socket_path(Some("my-app")), socket_path);
+    }
+    
+    /// Spawns with the given options and returns the diagnostics of the expected failure
+    fn spawn_diagnostics(options: SpawnOptions) -> SpawnDiagnostics {
+        match spawn_mpv("video.mkv", &SpawnOptions { skip_config_validation: true, ..options }) {
+            Err(Error::SpawnFailed(diagnostics)) => *diagnostics,
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok((mut child, _)) => {
+                let _ = child.kill();
+                panic!("spawn unexpectedly succeeded");
+            }
+        }
+    }
+    
+    /// Writes a shell script standing in for mpv, with the given permission bits
+    #[cfg(unix)]
+    fn fake_mpv(script: &str, mode: u32) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        
+        let path = std::env::temp_dir().join(format!("playa-mpv-{}", Uuid::new_v4()));
+        fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(mode)).unwrap();
+        path
+    }
+    
+    #[test]
+    fn missing_binary_fails_while_resolving_it() {
+        let diagnostics = spawn_diagnostics(SpawnOptions {
+            mpv_path: Some(PathBuf::from("/nonexistent/mpv")),
+            ..Default::default()
+        });
+        
+        assert_eq!(diagnostics.stage, SpawnStage::ResolveBinary);
+        assert_eq!(diagnostics.program, "/nonexistent/mpv");
+        assert!(diagnostics.args.is_empty());
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn invalid_options_fail_while_building_args() {
+        let mpv = fake_mpv("exec sleep 30", 0o755);
+        let diagnostics = spawn_diagnostics(SpawnOptions {
+            mpv_path: Some(mpv.clone()),
+            log_level: Some("not a level".to_string()),
+            ..Default::default()
+        });
+        let _ = fs::remove_file(&mpv);
+        
+        assert_eq!(diagnostics.stage, SpawnStage::BuildArgs);
+        assert!(diagnostics.cause.contains("Invalid log level"));
+        assert!(diagnostics.args.is_empty());
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn forced_launch_failure_keeps_the_attempted_args() {
+        // Without any execute bit the process cannot be started
+        let mpv = fake_mpv("exit 0", 0o644);
+        let diagnostics = spawn_diagnostics(SpawnOptions {
+            mpv_path: Some(mpv.clone()),
+            socket_path: Some(SOCKET.to_string()),
+            extra_args: vec!["--volume=50".to_string()],
+            ..Default::default()
+        });
+        let _ = fs::remove_file(&mpv);
+        
+        assert_eq!(diagnostics.stage, SpawnStage::Launch);
+        assert_eq!(diagnostics.program, mpv.display().to_string());
+        assert!(diagnostics.args.contains(&format!("--input-ipc-server={}", SOCKET)));
+        assert!(diagnostics.args.contains(&"--volume=50".to_string()));
+        assert!(diagnostics.args.contains(&"video.mkv".to_string()));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn exit_before_the_socket_exists_fails_the_launch() {
+        let mpv = fake_mpv("exit 3", 0o755);
+        let started = std::time::Instant::now();
+        let diagnostics = spawn_diagnostics(SpawnOptions {
+            mpv_path: Some(mpv.clone()),
+            ..Default::default()
+        });
+        let _ = fs::remove_file(&mpv);
+        
+        assert_eq!(diagnostics.stage, SpawnStage::Launch);
+        assert!(diagnostics.cause.contains("exited during startup"));
+        // The exit is noticed without waiting out the socket timeout
+        assert!(started.elapsed() < std::time::Duration::from_millis(SOCKET_CREATE_TIMEOUT_MS));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn socket_that_never_appears_fails_after_the_timeout() {
+        let mpv = fake_mpv("exec sleep 30", 0o755);
+        let socket_path = std::env::temp_dir().join(format!("playa-test-{}.sock", Uuid::new_v4()));
+        let diagnostics = spawn_diagnostics(SpawnOptions {
+            mpv_path: Some(mpv.clone()),
+            socket_path: Some(socket_path.display().to_string()),
+            ..Default::default()
+        });
+        let _ = fs::remove_file(&mpv);
+        
+        assert_eq!(diagnostics.stage, SpawnStage::SocketCreate);
+        assert!(diagnostics.cause.contains("Socket file not created"));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn spawn_waits_for_a_socket_created_after_launch() {
+        let mock = crate::core::player::mock::MockMpv::start();
+        let socket_path = std::env::temp_dir().join(format!("playa-test-{}.sock", Uuid::new_v4()));
+        let link = socket_path.clone();
+        let target = mock.socket_path();
+        let creator = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(300));
+            std::os::unix::fs::symlink(target, link).unwrap();
+        });
+        
+        let result = spawn_mpv("video.mkv", &SpawnOptions {
+            mpv_path: Some(mock.fake_binary()),
+            socket_path: Some(socket_path.display().to_string()),
+            skip_config_validation: true,
+            ..Default::default()
+        });
+        creator.join().unwrap();
+        let _ = fs::remove_file(&socket_path);
+        
+        let (mut child, _) = result.unwrap();
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+    
+    // Mode changes on per-process proc files are refused even for root
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn unchangeable_socket_fails_while_setting_permissions() {
+        let mpv = fake_mpv("exec sleep 30", 0o755);
+        let diagnostics = spawn_diagnostics(SpawnOptions {
+            mpv_path: Some(mpv.clone()),
+            socket_path: Some("/proc/self/status".to_string()),
+            socket_mode: Some(0o600),
+            ..Default::default()
+        });
+        let _ = fs::remove_file(&mpv);
+        
+        assert_eq!(diagnostics.stage, SpawnStage::SocketPermissions);
+        assert!(diagnostics.cause.contains("Failed to set socket permissions"));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn socket_is_created_in_a_missing_socket_dir() {
+        let mpv = fake_mpv(
+            "for arg; do case \"$arg\" in --input-ipc-server=*) touch \"${arg#*=}\";; esac; done\nexec sleep 30",
+            0o755,
+        );
+        // Kept short, the socket name alone takes up half of what a socket path may hold
+        let dir = std::env::temp_dir().join(format!("playa-{}", &Uuid::new_v4().to_string()[..8])).join("nested");
+        
+        let result = spawn_mpv("video.mkv", &SpawnOptions {
+            mpv_path: Some(mpv.clone()),
+            socket_dir: Some(dir.clone()),
+            instance_tag: Some("test".to_string()),
+            skip_config_validation: true,
+            ..Default::default()
+        });
+        let _ = fs::remove_file(&mpv);
+        
+        let (mut child, socket_path) = result.unwrap();
+        let _ = child.kill();
+        let _ = child.wait();
+        let _ = fs::remove_dir_all(dir.parent().unwrap());
+        
+        assert_eq!(Path::new(&socket_path).parent(), Some(dir.as_path()));
+        assert!(socket_path.contains("mpv-socket-test-"));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn unusable_socket_dir_is_a_config_error() {
+        let file = std::env::temp_dir().join(format!("playa-not-a-dir-{}", Uuid::new_v4()));
+        fs::write(&file, "").unwrap();
+        let spawn_in = |dir: PathBuf| spawn_mpv("video.mkv", &SpawnOptions {
+            socket_dir: Some(dir),
+            skip_config_validation: true,
+            ..Default::default()
+        });
+        
+        let result = spawn_in(file.join("sockets"));
+        let _ = fs::remove_file(&file);
+        assert!(matches!(result, Err(Error::ConfigError(message)) if message.contains("Failed to create socket directory")));
+        
+        // Files cannot be created directly in /proc, even by root
+        #[cfg(target_os = "linux")]
+        assert!(matches!(
+            spawn_in(PathBuf::from("/proc/self")),
+            Err(Error::ConfigError(message)) if message.contains("not writable")
+        ));
+    }
+    
+    #[test]
+    fn msg_level_is_passed_exactly_once() {
+        let msg_levels = |options: SpawnOptions| {
+            let args = args_for(&options);
+            if let Some(log_file) = &options.log_file {
+                let _ = fs::remove_file(log_file);
+            }
+            args.into_iter().filter(|arg| arg.starts_with("--msg-level")).collect::<Vec<_>>()
+        };
+        let log_file = || Some(std::env::temp_dir().join(format!("playa-log-{}.txt", Uuid::new_v4())));
+        
+        assert_eq!(msg_levels(SpawnOptions::default()), ["--msg-level=all=v"]);
+        assert_eq!(msg_levels(SpawnOptions {
+            log_level: Some("all=warn".to_string()),
+            ..Default::default()
+        }), ["--msg-level=all=warn"]);
+        assert_eq!(msg_levels(SpawnOptions {
+            log_file: log_file(),
+            ..Default::default()
+        }), ["--msg-level=all=v"]);
+        assert_eq!(msg_levels(SpawnOptions {
+            log_file: log_file(),
+            log_level: Some("all=warn,vo=v".to_string()),
+            ..Default::default()
+        }), ["--msg-level=all=warn,vo=v"]);
+    }
+}
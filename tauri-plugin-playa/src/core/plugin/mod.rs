@@ -1,27 +1,29 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
+use std::process::Child;
 use std::thread::{self, JoinHandle};
-use tokio::sync::mpsc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
 use tokio::task::JoinHandle as TokioJoinHandle;
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use log::{debug, error};
+use log::{debug, error, warn};
 
-use crate::core::player::process::{SpawnOptions, spawn_mpv};
-use crate::core::player::ipc::MpvIpcClient;
-use crate::core::player::events::MpvEventListener;
+use crate::core::player::process::{HrSeek, KeepOpen, OscMode, OutputCapture, SpawnOptions, TrackSelector, spawn_mpv_playlist, spawn_mpv_with_output};
+use crate::core::player::ipc::{AudioChannels, CacheStats, ChapterInfo, DebandParams, LoadMode, LoopMode, MpvIpcClient, PlaybackState, SeekMode, PlaylistEntry, Track, TrackInfo, VideoTransform, chapter_loop_bounds};
+use crate::core::player::events::{CloseReason, MpvEvent, MpvEventListener};
 use crate::core::config::ipc::{IpcConfig, DEFAULT_IPC_POLL_INTERVAL_MS, DEFAULT_MAX_RECONNECT_ATTEMPTS, DEFAULT_RECONNECT_DELAY_MS};
 
 use crate::{Result, Error};
 
-use std::collections::HashSet;
-use lazy_static::lazy_static;
+pub mod pool;
 
-// Global event deduplication cache
-lazy_static! {
-    static ref NOTIFIED_EVENTS: Mutex<HashMap<String, HashSet<String>>> = Mutex::new(HashMap::new());
-}
+pub use pool::VideoManagerPool;
+
+use std::collections::HashSet;
 
 /// A unique identifier for a video instance
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -50,18 +52,33 @@ impl VideoId {
 /// Window configuration options
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct WindowOptions {
-    /// Whether to use a borderless window
-    pub borderless: bool,
+    /// Whether to use a borderless window; unset inherits the manager default
+    pub borderless: Option<bool>,
     /// Window position (x, y) relative to screen
     pub position: Option<(i32, i32)>,
     /// Window size (width, height)
     pub size: Option<(u32, u32)>,
-    /// Whether to make the window always on top
-    pub always_on_top: bool,
+    /// Whether to make the window always on top; unset inherits the manager default
+    pub always_on_top: Option<bool>,
     /// Alpha value for window transparency (0.0-1.0)
     pub opacity: Option<f32>,
-    /// Whether to hide window on startup
-    pub start_hidden: bool,
+    /// Whether to hide window on startup; unset inherits the manager default
+    pub start_hidden: Option<bool>,
+}
+
+impl WindowOptions {
+    /// Merges these options over a set of defaults, field by field.
+    /// Values set here take precedence, including flags explicitly set to `false`.
+    pub fn merged_over(&self, defaults: &WindowOptions) -> WindowOptions {
+        WindowOptions {
+            borderless: self.borderless.or(defaults.borderless),
+            position: self.position.or(defaults.position),
+            size: self.size.or(defaults.size),
+            always_on_top: self.always_on_top.or(defaults.always_on_top),
+            opacity: self.opacity.or(defaults.opacity),
+            start_hidden: self.start_hidden.or(defaults.start_hidden),
+        }
+    }
 }
 
 /// Options for video playback
@@ -79,10 +96,121 @@ pub struct PlaybackOptions {
     pub report_progress: bool,
     /// Progress reporting interval in milliseconds
     pub progress_interval_ms: Option<u64>,
+    /// Single cadence in milliseconds for both the playback monitor and the event listener,
+    /// taking precedence over `progress_interval_ms`. Values below 100ms are clamped to 100ms.
+    pub poll_interval_ms: Option<u64>,
     /// Window configuration options
     pub window: Option<WindowOptions>,
     /// Connection timeout in milliseconds
     pub connection_timeout_ms: Option<u64>,
+    /// External chapters file (XML or ffmetadata) to load alongside the media
+    pub chapters_file: Option<PathBuf>,
+    /// Whether mpv keeps the window open after playback ends; unset leaves it to the mpv config
+    #[serde(default)]
+    pub keep_open: Option<KeepOpen>,
+    /// Application data associated with the video, retrievable via `VideoManager::get_user_data`
+    pub user_data: Option<serde_json::Value>,
+    /// Tag embedded in the IPC socket name so other tools can tell which app owns the instance
+    pub instance_tag: Option<String>,
+    /// Whether to preserve audio pitch when playback speed changes
+    #[serde(default = "default_true")]
+    pub pitch_correction: bool,
+    /// Scale factor for OSD text, e.g. for readability on TVs
+    pub osd_scale: Option<f64>,
+    /// Explicit IPC socket path; a unique one is generated when unset
+    pub socket_path: Option<String>,
+    /// Audio track to select from the first frame
+    pub initial_audio: Option<TrackSelector>,
+    /// Subtitle track to select from the first frame
+    pub initial_subtitle: Option<TrackSelector>,
+    /// Default precision for every seek on the instance
+    #[serde(default)]
+    pub hr_seek: HrSeek,
+    /// Trades smoothness for latency, e.g. for local screen mirroring
+    #[serde(default)]
+    pub low_latency: bool,
+    /// Signals the playback monitor polls mpv for
+    #[serde(default)]
+    pub observe: ObserveSet,
+    /// Subtitle font that overrides the file's own styles
+    pub sub_font: Option<String>,
+    /// Subtitle font size (scaled to a 720p window height)
+    pub sub_font_size: Option<u32>,
+    /// Unix permission bits for the IPC socket, e.g. `0o600` on multi-user machines
+    pub socket_mode: Option<u32>,
+    /// mpv binary to launch instead of the one found on PATH (e.g. one bundled with the app)
+    pub mpv_path: Option<PathBuf>,
+    /// Seconds of a network stream to buffer with the window minimized before revealing it
+    pub prebuffer_secs: Option<f64>,
+    /// File the played stream is recorded to as it is received
+    pub record_to: Option<PathBuf>,
+    /// File mpv writes its full log to, for diagnosing a single playback
+    pub log_file: Option<PathBuf>,
+    /// Environment variables set on the mpv process, on top of the inherited environment
+    #[serde(default)]
+    pub env: Vec<(String, String)>,
+    /// Whether to capture mpv's stdout/stderr, retrievable via `VideoManager::get_output`
+    #[serde(default)]
+    pub capture_output: bool,
+    /// Directory the generated IPC socket is created in instead of `/tmp` (Unix only)
+    pub socket_dir: Option<PathBuf>,
+    /// mpv `--msg-level` value such as `all=warn`; defaults to `all=v`
+    pub log_level: Option<String>,
+    /// Number of output lines kept when `capture_output` is set
+    pub output_max_lines: Option<usize>,
+    /// Whether mpv loads the bundled config (uosc, fonts, mpv.conf) instead of the user's own
+    #[serde(default = "default_true")]
+    pub use_bundled_config: bool,
+    /// Which on-screen controller to show, see `OscMode`
+    #[serde(default)]
+    pub osc_mode: OscMode,
+}
+
+/// Selects which signals the playback monitor polls mpv for on each tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ObserveSet {
+    /// `Progress` events (position and duration)
+    pub progress: bool,
+    /// `Paused`/`Resumed` events for pauses not made through the manager
+    pub pause_state: bool,
+    /// `Ended`/`Closed` detection (pid, playback status, EOF and idle state)
+    pub lifecycle: bool,
+}
+
+impl ObserveSet {
+    /// Only progress updates
+    pub fn progress_only() -> Self {
+        Self {
+            progress: true,
+            pause_state: false,
+            lifecycle: false,
+        }
+    }
+}
+
+impl Default for ObserveSet {
+    fn default() -> Self {
+        Self {
+            progress: true,
+            pause_state: true,
+            lifecycle: true,
+        }
+    }
+}
+
+impl PlaybackOptions {
+    /// The interval both the playback monitor and the event listener poll mpv at
+    pub fn effective_poll_interval_ms(&self) -> u64 {
+        self.poll_interval_ms
+            .or(self.progress_interval_ms)
+            .unwrap_or(DEFAULT_IPC_POLL_INTERVAL_MS)
+            .max(MIN_POLL_INTERVAL_MS)
+    }
+}
+
+fn default_true() -> bool {
+    true
 }
 
 impl Default for PlaybackOptions {
@@ -94,8 +222,35 @@ impl Default for PlaybackOptions {
             title: None,
             report_progress: true,
             progress_interval_ms: Some(1000),
+            poll_interval_ms: None,
             window: None,
             connection_timeout_ms: None,
+            chapters_file: None,
+            keep_open: None,
+            user_data: None,
+            instance_tag: None,
+            pitch_correction: true,
+            osd_scale: None,
+            socket_path: None,
+            initial_audio: None,
+            initial_subtitle: None,
+            hr_seek: HrSeek::Default,
+            low_latency: false,
+            observe: ObserveSet::default(),
+            sub_font: None,
+            sub_font_size: None,
+            socket_mode: None,
+            mpv_path: None,
+            prebuffer_secs: None,
+            record_to: None,
+            log_file: None,
+            env: Vec::new(),
+            capture_output: false,
+            socket_dir: None,
+            log_level: None,
+            output_max_lines: None,
+            use_bundled_config: true,
+            osc_mode: OscMode::Bundled,
         }
     }
 }
@@ -120,52 +275,251 @@ pub enum VideoEvent {
     Ended { id: VideoId },
     /// Video instance closed
     Closed { id: VideoId },
+    /// The last playlist entry finished playing
+    PlaylistEnded { id: VideoId },
     /// Error occurred
     Error { id: VideoId, message: String },
+    /// The video resolution changed mid-stream (e.g. an adaptive stream switched quality)
+    VideoParamsChanged { id: VideoId, width: i64, height: i64 },
+    /// A seek finished and the frame at the new position is ready to display
+    SeekCompleted { id: VideoId, position: f64 },
+    /// A script (e.g. a custom uosc button) sent a `script-message` to the application
+    ScriptMessage { id: VideoId, args: Vec<String> },
+    /// The file's metadata tags changed (e.g. the current song on an internet radio stream)
+    MetadataChanged { id: VideoId, metadata: HashMap<String, String> },
+    /// Playback stalled to fill the cache of a network stream; `percent` is the fill level
+    Buffering { id: VideoId, percent: f64 },
+    /// Enough was buffered for playback to continue
+    BufferingDone { id: VideoId },
 }
 
-/// A subscription to video events with async support
+/// Number of events buffered for subscribers before the slowest ones start lagging
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// An event tagged with its position in the manager's event stream
+type SequencedEvent = (u64, VideoEvent);
+
+/// A subscription to video events with async support.
+///
+/// A subscriber that falls more than `EVENT_CHANNEL_CAPACITY` events behind skips the
+/// oldest ones (counted by `lagged`), except `Closed`, `Ended` and `Error`, which are
+/// always delivered in order.
 pub struct EventSubscription {
-    receiver: mpsc::Receiver<VideoEvent>,
+    receiver: broadcast::Receiver<SequencedEvent>,
+    /// Unbounded copy of the terminal events, to recover the ones skipped while lagging
+    terminal: mpsc::UnboundedReceiver<SequencedEvent>,
+    terminal_backlog: VecDeque<SequencedEvent>,
+    pending: VecDeque<VideoEvent>,
+    last_seq: Option<u64>,
+    lagged: u64,
     _id: Uuid,
 }
 
 impl EventSubscription {
     /// Receives the next event, blocking until one is available
     pub async fn recv(&mut self) -> Option<VideoEvent> {
-        self.receiver.recv().await
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(event);
+            }
+            
+            match self.receiver.recv().await {
+                Ok((seq, event)) => {
+                    self.recover_terminal_events(seq);
+                    self.last_seq = Some(seq);
+                    self.pending.push_back(event);
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Event subscriber lagged behind, skipped {} events", skipped);
+                    self.lagged += skipped;
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    self.recover_terminal_events(u64::MAX);
+                    if self.pending.is_empty() {
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+    
+    /// Receives events until one matches `pred` and returns it, discarding the others.
+    /// Fails with `Error::EventTimeout` if none matches within `timeout`.
+    pub async fn wait_for<F: Fn(&VideoEvent) -> bool>(&mut self, pred: F, timeout: Duration) -> Result<VideoEvent> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        
+        loop {
+            match tokio::time::timeout_at(deadline, self.recv()).await {
+                Ok(Some(event)) if pred(&event) => return Ok(event),
+                Ok(Some(_)) => continue,
+                Ok(None) => return Err(Error::MpvError("Event stream closed while waiting for an event".to_string())),
+                Err(_) => return Err(Error::EventTimeout { timeout_ms: timeout.as_millis() as u64 }),
+            }
+        }
+    }
+    
+    /// Returns how many events this subscriber has skipped by falling behind
+    pub fn lagged(&self) -> u64 {
+        self.lagged
+    }
+    
+    /// Queues the terminal events sent before `seq` that were skipped while lagging
+    fn recover_terminal_events(&mut self, seq: u64) {
+        while let Ok(entry) = self.terminal.try_recv() {
+            self.terminal_backlog.push_back(entry);
+        }
+        
+        while self.terminal_backlog.front().is_some_and(|(terminal_seq, _)| *terminal_seq <= seq) {
+            let (terminal_seq, event) = self.terminal_backlog.pop_front().unwrap();
+            let received = terminal_seq == seq || self.last_seq.is_some_and(|last| terminal_seq <= last);
+            if !received {
+                self.pending.push_back(event);
+            }
+        }
+    }
+}
+
+/// Returns whether the entry that just ended was the last one in mpv's `playlist`.
+/// Falls back to the `current`/`playing` flag when mpv does not report entry IDs.
+fn is_last_playlist_entry(playlist: &[serde_json::Value], ended_entry_id: Option<i64>) -> bool {
+    let last = match playlist.last() {
+        Some(last) => last,
+        // An empty playlist has nothing left to play
+        None => return true,
+    };
+    
+    match (ended_entry_id, last.get("id").and_then(|id| id.as_i64())) {
+        (Some(ended), Some(last_id)) => ended == last_id,
+        _ => ["current", "playing"].iter()
+            .any(|flag| last.get(*flag).and_then(|v| v.as_bool()).unwrap_or(false)),
     }
 }
 
-/// Internal event subscriber
+/// Flattens mpv's `metadata` property into string tags, stringifying any non-string values
+fn parse_metadata(value: &serde_json::Value) -> HashMap<String, String> {
+    value.as_object()
+        .map(|tags| tags.iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                (key.clone(), value)
+            })
+            .collect())
+        .unwrap_or_default()
+}
+
+/// Internal event subscriber, receiving the terminal events that must not be lost
 #[derive(Clone)]
 struct EventSubscriber {
     id: Uuid,
-    sender: mpsc::Sender<VideoEvent>,
+    terminal_sender: mpsc::UnboundedSender<SequencedEvent>,
+}
+
+/// Shared event fan-out state for a VideoManager
+///
+/// Terminal events are deduplicated here rather than per thread, so a `Closed`
+/// notified from a control method and one from the monitor thread are treated alike.
+struct EventHub {
+    sender: broadcast::Sender<SequencedEvent>,
+    subscribers: Mutex<Vec<EventSubscriber>>,
+    /// Sequence number of the next event, locked while an event is sent so the
+    /// broadcast and terminal channels see events in the same order
+    next_seq: Mutex<u64>,
+    notified_events: Mutex<HashMap<VideoId, HashSet<&'static str>>>,
+}
+
+impl Default for EventHub {
+    fn default() -> Self {
+        Self {
+            sender: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
+            subscribers: Mutex::new(Vec::new()),
+            next_seq: Mutex::new(0),
+            notified_events: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl EventHub {
+    /// Records a terminal event for a video, returning false if it was already notified
+    fn mark_notified(&self, id: VideoId, event_type: &'static str) -> bool {
+        let mut events = self.notified_events.lock().unwrap();
+        events.entry(id).or_insert_with(HashSet::new).insert(event_type)
+    }
+    
+    /// Drops the deduplication state of a video once it has been fully closed
+    fn forget(&self, id: VideoId) {
+        self.notified_events.lock().unwrap().remove(&id);
+    }
 }
 
 /// Internal representation of a video instance
 #[allow(dead_code)]
 struct VideoInstance {
     id: VideoId,
-    process: std::process::Child,
+    /// The mpv process, or `None` once ownership has been released via `detach`
+    process: Option<Child>,
     ipc_client: Arc<Mutex<MpvIpcClient>>,
     event_listener: Option<MpvEventListener>,
     event_thread: Option<JoinHandle<()>>,
     socket_path: String,
+    user_data: Option<serde_json::Value>,
+    instance_tag: Option<String>,
+    /// Position in seconds at which playback should be paused, set via `pause_at`
+    pause_target: Arc<Mutex<Option<f64>>>,
+    /// Settings to re-apply if the connection is restored to a restarted mpv
+    client_state: Arc<Mutex<ClientState>>,
+    /// Seek/volume requests waiting to be sent when command debouncing is enabled
+    pending_controls: Arc<Mutex<PendingControls>>,
+    /// Loop mode set via `set_loop`, so the monitor keeps running through EOF while looping
+    loop_mode: Arc<Mutex<LoopMode>>,
+    /// Set by `load_file` so the monitor restarts its change tracking for the new file
+    monitor_reset: Arc<Mutex<bool>>,
+    /// mpv's recent stdout/stderr, when spawned with `capture_output`
+    output: Option<OutputCapture>,
+}
+
+/// Settings the playback monitor runs with, taken from `PlaybackOptions`
+struct MonitorSettings {
+    interval_ms: u64,
+    keep_open: Option<KeepOpen>,
+    observe: ObserveSet,
+    prebuffer_secs: Option<f64>,
+}
+
+/// Per-instance state the manager's control methods share with the playback monitor
+struct MonitorControls {
+    pause_target: Arc<Mutex<Option<f64>>>,
+    loop_mode: Arc<Mutex<LoopMode>>,
+    reset: Arc<Mutex<bool>>,
+}
+
+/// Latest values of debounced control commands for a video instance
+#[derive(Default)]
+struct PendingControls {
+    seek: Option<f64>,
+    volume: Option<f64>,
+    flush_scheduled: bool,
 }
 
 impl Drop for VideoInstance {
     fn drop(&mut self) {
         debug!("Dropping VideoInstance with ID: {}", self.id.to_string());
         
+        // A released instance already closed IPC and handed its process off, so mpv must not be told to quit
+        if self.process.is_none() {
+            debug!("VideoInstance {} was released, leaving mpv running", self.id.to_string());
+            return;
+        }
+        
         // First, stop the event listener to prevent any further IPC communication
         if let Some(mut event_listener) = self.event_listener.take() {
             debug!("Stopping event listener for video {}", self.id.to_string());
             let _ = event_listener.stop_listening();
             let _ = event_listener.handle_process_exit();
         }
-
+        
         // Attempt to quit mpv gracefully and mark IPC as intentionally closed
         if let Some(mut client) = self.ipc_client.lock().ok() {
             debug!("Sending quit command to mpv for video {}", self.id.to_string());
@@ -182,7 +536,9 @@ impl Drop for VideoInstance {
         sleep(Duration::from_millis(100));
         
         // Kill the process if it's still running
-        let _ = self.process.kill();
+        if let Some(process) = self.process.as_mut() {
+            let _ = process.kill();
+        }
         
         // Join the event thread if it exists
         if let Some(thread) = self.event_thread.take() {
@@ -227,11 +583,138 @@ pub struct PlaybackProgress {
     pub is_paused: bool,
 }
 
+/// How a playback started with `VideoManager::play_and_wait` went
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaybackOutcome {
+    /// Last playback position reported before the video stopped, in seconds
+    pub watched_seconds: f64,
+    /// Whether the video played to the end rather than being closed or timing out
+    pub completed: bool,
+}
+
+/// Playback settings applied by the application that are re-pushed if mpv restarts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClientState {
+    /// Volume (0-100)
+    pub volume: Option<f64>,
+    /// Playback speed
+    pub speed: Option<f64>,
+    /// Whether audio is muted
+    pub muted: Option<bool>,
+    /// Selected audio track ID
+    pub audio_track: Option<i64>,
+    /// Selected subtitle track ID
+    pub subtitle_track: Option<i64>,
+    /// Subtitle delay in seconds
+    pub sub_delay: Option<f64>,
+}
+
+impl ClientState {
+    /// Reads the current settings from mpv
+    pub fn capture(client: &mut MpvIpcClient) -> Self {
+        Self {
+            volume: client.get_volume().ok(),
+            speed: client.get_speed().ok(),
+            muted: client.get_mute().ok(),
+            audio_track: client.get_current_audio_track().ok().flatten(),
+            subtitle_track: client.get_current_subtitle_track().ok().flatten(),
+            sub_delay: client.get_sub_delay().ok(),
+        }
+    }
+    
+    /// Pushes every recorded setting to mpv
+    pub fn apply(&self, client: &mut MpvIpcClient) -> Result<()> {
+        if let Some(volume) = self.volume {
+            client.set_volume(volume)?;
+        }
+        if let Some(speed) = self.speed {
+            client.set_speed(speed)?;
+        }
+        if let Some(muted) = self.muted {
+            client.set_mute(muted)?;
+        }
+        if let Some(audio_track) = self.audio_track {
+            client.set_audio_track(audio_track)?;
+        }
+        if let Some(subtitle_track) = self.subtitle_track {
+            client.set_subtitle_track(subtitle_track)?;
+        }
+        if let Some(sub_delay) = self.sub_delay {
+            client.set_sub_delay(sub_delay)?;
+        }
+        Ok(())
+    }
+}
+
+/// Smallest playback speed accepted by `VideoManager::set_speed`
+const MIN_PLAYBACK_SPEED: f64 = 0.01;
+
+/// Interval between process status checks in `VideoManager::wait_for_exit`
+const EXIT_POLL_INTERVAL_MS: u64 = 100;
+
+/// Interval between volume steps while fading for `VideoManager::duck`
+const DUCK_FADE_STEP_MS: u64 = 20;
+
+/// Shortest poll interval accepted by `PlaybackOptions::poll_interval_ms`
+const MIN_POLL_INTERVAL_MS: u64 = 100;
+
+/// Longest wait for `PlaybackOptions::prebuffer_secs` before revealing the window anyway
+const PREBUFFER_TIMEOUT_MS: u64 = 10000;
+
+/// Interval between cache checks while pre-buffering
+const PREBUFFER_POLL_INTERVAL_MS: u64 = 100;
+
+/// Longest wait for a busy video manager in `VideoManager::shutdown_shared`
+const SHUTDOWN_LOCK_TIMEOUT_MS: u64 = 1000;
+
+/// Restores a video's volume when dropped; returned by `VideoManager::duck`
+pub struct DuckGuard {
+    id: VideoId,
+    client: Arc<Mutex<MpvIpcClient>>,
+    previous_volume: f64,
+}
+
+impl DuckGuard {
+    /// The volume that will be restored when the guard drops
+    pub fn previous_volume(&self) -> f64 {
+        self.previous_volume
+    }
+}
+
+impl Drop for DuckGuard {
+    fn drop(&mut self) {
+        debug!("Restoring volume {} for video {}", self.previous_volume, self.id.to_string());
+        if let Ok(mut client) = self.client.lock() {
+            if let Err(e) = client.set_volume(self.previous_volume) {
+                error!("Failed to restore volume for video {}: {}", self.id.to_string(), e);
+            }
+        }
+    }
+}
+
+/// An explicit socket path claimed by a video that is still starting, released when dropped.
+/// Once the instance is registered its own `socket_path` keeps the path taken.
+struct SocketReservation {
+    socket_path: String,
+    reserved: Arc<Mutex<HashSet<String>>>,
+}
+
+impl Drop for SocketReservation {
+    fn drop(&mut self) {
+        self.reserved.lock().unwrap().remove(&self.socket_path);
+    }
+}
+
 /// Manager for video instances with async support
 pub struct VideoManager {
     instances: Arc<Mutex<HashMap<VideoId, VideoInstance>>>,
-    event_subscribers: Arc<Mutex<Vec<EventSubscriber>>>,
+    event_subscribers: Arc<EventHub>,
     _event_task: Option<TokioJoinHandle<()>>,
+    default_window: Option<WindowOptions>,
+    command_debounce: Option<Duration>,
+    kill_on_drop: bool,
+    connecting: Arc<Mutex<HashSet<VideoId>>>,
+    reserved_sockets: Arc<Mutex<HashSet<String>>>,
 }
 
 impl VideoManager {
@@ -239,89 +722,334 @@ impl VideoManager {
     pub fn new() -> Self {
         Self {
             instances: Arc::new(Mutex::new(HashMap::new())),
-            event_subscribers: Arc::new(Mutex::new(Vec::new())),
+            event_subscribers: Arc::new(EventHub::default()),
             _event_task: None,
+            default_window: None,
+            command_debounce: None,
+            kill_on_drop: true,
+            connecting: Arc::new(Mutex::new(HashSet::new())),
+            reserved_sockets: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+    
+    /// Sets the window options inherited by every subsequent `play` call.
+    /// Per-call `PlaybackOptions.window` values override these field by field.
+    pub fn with_default_window(mut self, window: WindowOptions) -> Self {
+        self.default_window = Some(window);
+        self
+    }
+    
+    /// Coalesces rapid `seek`/`set_volume` calls, sending only the latest value once `window` has elapsed.
+    /// Debounced calls return before the command reaches mpv; failures are logged instead.
+    pub fn with_command_debounce(mut self, window: Duration) -> Self {
+        self.command_debounce = Some(window);
+        self
+    }
+    
+    /// Controls whether mpv processes are terminated when the manager is dropped (the default).
+    /// When disabled, dropping the manager only closes IPC and leaves every mpv running;
+    /// those processes are no longer tracked and must be cleaned up by the caller or the user.
+    pub fn with_kill_on_drop(mut self, kill_on_drop: bool) -> Self {
+        self.kill_on_drop = kill_on_drop;
+        self
+    }
+    
+    /// Records a debounced control value and schedules a flush if none is pending
+    fn queue_control(instance: &VideoInstance, window: Duration, update: impl FnOnce(&mut PendingControls)) {
+        let mut pending = instance.pending_controls.lock().unwrap();
+        update(&mut pending);
+        
+        if pending.flush_scheduled {
+            return;
+        }
+        pending.flush_scheduled = true;
+        
+        let id = instance.id;
+        let pending_controls = Arc::clone(&instance.pending_controls);
+        let client = instance.ipc_client.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(window).await;
+            
+            let (seek, volume) = {
+                let mut pending = pending_controls.lock().unwrap();
+                pending.flush_scheduled = false;
+                (pending.seek.take(), pending.volume.take())
+            };
+            
+            let mut client = client.lock().unwrap();
+            if let Some(position) = seek {
+                if let Err(e) = client.seek(position) {
+                    error!("Debounced seek failed for video {}: {}", id.to_string(), e);
+                }
+            }
+            if let Some(volume) = volume {
+                if let Err(e) = client.set_volume(volume) {
+                    error!("Debounced volume change failed for video {}: {}", id.to_string(), e);
+                }
+            }
+        });
+    }
+    
+    /// The error for a control call on an id with no registered instance
+    fn missing_instance(&self, id: VideoId) -> Error {
+        if self.connecting.lock().unwrap().contains(&id) {
+            Error::MpvError("IPC not yet ready".to_string())
+        } else {
+            Error::MpvError(format!("Video instance not found: {}", id.to_string()))
+        }
+    }
+    
+    /// Claims an explicit socket path before spawning mpv on it, refusing one that a live
+    /// or still-starting video is already bound to. Generated paths need no reservation.
+    fn reserve_socket(&self, socket_path: Option<&str>) -> Result<Option<SocketReservation>> {
+        let Some(socket_path) = socket_path else {
+            return Ok(None);
+        };
+        
+        // Hold the reservations while checking instances so two plays cannot both pass
+        let mut reserved = self.reserved_sockets.lock().unwrap();
+        let in_use_by = self.instances.lock().unwrap().values()
+            .find(|i| i.socket_path == socket_path)
+            .map(|existing| format!("video {}", existing.id.to_string()));
+        let in_use_by = in_use_by.or_else(|| reserved.contains(socket_path).then(|| "a starting video".to_string()));
+        
+        if let Some(owner) = in_use_by {
+            return Err(Error::ConfigError(format!("Socket path {} is already in use by {}", socket_path, owner)));
+        }
+        
+        reserved.insert(socket_path.to_string());
+        Ok(Some(SocketReservation {
+            socket_path: socket_path.to_string(),
+            reserved: Arc::clone(&self.reserved_sockets),
+        }))
+    }
+    
+    /// Resolves the effective window options for a play call
+    fn resolve_window(&self, window: Option<WindowOptions>) -> Option<WindowOptions> {
+        match (window, &self.default_window) {
+            (Some(window), Some(defaults)) => Some(window.merged_over(defaults)),
+            (Some(window), None) => Some(window),
+            (None, defaults) => defaults.clone(),
         }
     }
     
     /// Plays a video from a local file or URL
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, options), fields(video_id = tracing::field::Empty)))]
     pub async fn play(&self, source: String, options: PlaybackOptions) -> Result<VideoId> {
-        let instances = Arc::clone(&self.instances);
-        let event_subscribers = self.event_subscribers.clone();
+        let (id, ready) = self.play_async(source, options);
+        
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("video_id", id.to_string().as_str());
+        
+        ready.await.map(|_| id)
+    }
+    
+    /// Plays a queue of files or URLs as one instance, starting at the first entry.
+    /// Progress and info follow the entry currently playing; use `playlist_next` to advance.
+    pub async fn play_playlist(&self, sources: Vec<String>, options: PlaybackOptions) -> Result<VideoId> {
+        if sources.is_empty() {
+            return Err(Error::ConfigError("Playlist must contain at least one entry".to_string()));
+        }
+        
+        let (id, ready) = self.start_playback(sources, options);
+        ready.await.map(|_| id)
+    }
+    
+    /// Plays a video from synchronous code that has no tokio runtime, returning once it is playing.
+    /// Fails when called from inside a runtime, where `play` should be awaited instead.
+    pub fn play_blocking(&self, source: String, options: PlaybackOptions) -> Result<VideoId> {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Err(Error::MpvError(
+                "play_blocking cannot be called from within a tokio runtime, use play instead".to_string()
+            ));
+        }
+        
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| Error::MpvError(format!("Failed to create runtime for play_blocking: {}", e)))?;
+        
+        runtime.block_on(self.play(source, options))
+    }
+    
+    /// Plays a video and waits until it ends, is closed, or `max` elapses, then closes it.
+    /// Meant for scripts and demos that have nothing else to do while the video plays.
+    pub async fn play_and_wait(&self, source: String, options: PlaybackOptions, max: Duration) -> Result<PlaybackOutcome> {
+        let mut subscription = self.subscribe().await;
+        let (id, ready) = self.play_async(source, options);
+        
+        if let Err(e) = ready.await {
+            self.unsubscribe(subscription._id).await;
+            return Err(e);
+        }
+        
+        let mut outcome = PlaybackOutcome { watched_seconds: 0.0, completed: false };
+        let deadline = tokio::time::Instant::now() + max;
+        
+        while let Ok(Some(event)) = tokio::time::timeout_at(deadline, subscription.recv()).await {
+            match event {
+                VideoEvent::Progress { id: event_id, position, .. } if event_id == id => {
+                    outcome.watched_seconds = position;
+                },
+                VideoEvent::Ended { id: event_id } if event_id == id => {
+                    outcome.completed = true;
+                    break;
+                },
+                VideoEvent::Closed { id: event_id } | VideoEvent::Error { id: event_id, .. } if event_id == id => break,
+                _ => {},
+            }
+        }
+        
+        self.unsubscribe(subscription._id).await;
+        
+        // The video may already be gone if mpv was closed by the user
+        if let Err(e) = self.close(id).await {
+            debug!("Video {} was already closed: {}", id.to_string(), e);
+        }
+        
+        Ok(outcome)
+    }
+    
+    /// Starts playing a video and returns its ID right away, before mpv is up.
+    /// The returned future resolves once playback has started; events for the ID
+    /// flow as soon as they are available. Must be called within a Tokio runtime.
+    pub fn play_async(&self, source: String, options: PlaybackOptions) -> (VideoId, impl Future<Output = Result<()>> + Send + 'static) {
+        self.start_playback(vec![source], options)
+    }
+    
+    /// Spawns mpv for the given playlist in the background, as described on `play_async`
+    fn start_playback(&self, sources: Vec<String>, mut options: PlaybackOptions) -> (VideoId, impl Future<Output = Result<()>> + Send + 'static) {
+        options.window = self.resolve_window(options.window.take());
         
         // Generate a new video ID
         let id = VideoId::new();
         
-        // Spawn a blocking task to play the video
-        tokio::task::spawn_blocking(move || {
+        // Refuse to start a second mpv on a socket another video is bound to or starting on
+        let reservation = self.reserve_socket(options.socket_path.as_deref());
+        
+        let instances = Arc::clone(&self.instances);
+        let event_subscribers = self.event_subscribers.clone();
+        
+        // Spawn a blocking task to play the video right away, so it progresses even before being awaited
+        let task = reservation.map(|reservation| tokio::task::spawn_blocking(move || {
             // Fix the spawn_options creation
             let spawn_options = SpawnOptions::from(&options);
             
             // Fix the mpv spawn
-            let (mut process, socket_path) = spawn_mpv(&source, &spawn_options)?;
-            
-            // Fix the IpcConfig
-            let ipc_config = if options.connection_timeout_ms.is_some() {
-                IpcConfig::new(
-                    options.connection_timeout_ms.unwrap(),
-                    DEFAULT_IPC_POLL_INTERVAL_MS,  // Use default poll interval
-                    true,  // auto_reconnect
-                    DEFAULT_MAX_RECONNECT_ATTEMPTS,  // max_reconnect_attempts
-                    DEFAULT_RECONNECT_DELAY_MS  // reconnect_delay_ms
-                )
-            } else {
-                IpcConfig::default()
-            };
-            
-            // Fix the MpvIpcClient connection
-            let ipc_client = match MpvIpcClient::connect_with_config(&socket_path, ipc_config.clone()) {
-                Ok(client) => client,
-                Err(e) => {
-                    // If we can't connect, make sure to clean up the process
-                    debug!("Failed to connect to mpv IPC socket, killing process: {}", e);
-                    let _ = process.kill();
-                    return Err(e);
-                }
-            };
-            
-            let ipc_client = Arc::new(Mutex::new(ipc_client));
-            
-            // Fix the MpvIpcClient for events
-            let event_ipc_client = match MpvIpcClient::connect_with_config(&socket_path, ipc_config) {
-                Ok(client) => client,
-                Err(e) => {
-                    debug!("Failed to connect event listener to mpv IPC socket: {}", e);
-                    // Still return success, but without event listening
-                    let instance = VideoInstance {
-                        id,
-                        process,
-                        ipc_client,
-                        event_listener: None,
-                        event_thread: None,
-                        socket_path,
-                    };
-                    
-                    let mut instances = instances.lock().unwrap();
-                    instances.insert(id, instance);
-                    
-                    return Ok(id);
-                }
-            };
+            let (process, socket_path, output) = spawn_mpv_playlist(&sources, &spawn_options)?;
             
-            // Fix the MpvEventListener creation
-            let mut listener = MpvEventListener::new(event_ipc_client);
+            let result = Self::attach_process(id, process, socket_path, output, &options, &instances, &event_subscribers)
+                .map(|_| ());
+            drop(reservation);
+            result
+        }));
+        
+        let ready = async move {
+            task?.await.unwrap()
+        };
+        
+        (id, ready)
+    }
+    
+    /// Returns an ID right away, spawning mpv and connecting IPC in the background.
+    /// Until the connection is ready, control methods fail with the transient
+    /// `Error::MpvError("IPC not yet ready")`; if mpv fails to start or connect,
+    /// a `VideoEvent::Error` is emitted.
+    pub async fn play_detached(&self, source: String, mut options: PlaybackOptions) -> Result<VideoId> {
+        options.window = self.resolve_window(options.window.take());
+        
+        let reservation = self.reserve_socket(options.socket_path.as_deref())?;
+        let id = VideoId::new();
+        self.connecting.lock().unwrap().insert(id);
+        
+        let instances = Arc::clone(&self.instances);
+        let event_subscribers = self.event_subscribers.clone();
+        let connecting = Arc::clone(&self.connecting);
+        
+        // Spawning waits for mpv to create its socket, so it must not block the caller either
+        tokio::task::spawn_blocking(move || {
+            let result = spawn_mpv_with_output(&source, &SpawnOptions::from(&options))
+                .and_then(|(process, socket_path, output)| {
+                    Self::attach_process(id, process, socket_path, output, &options, &instances, &event_subscribers)
+                });
+            connecting.lock().unwrap().remove(&id);
+            drop(reservation);
             
-            // Start the listener
-            if let Err(e) = listener.start_listening() {
-                debug!("Failed to start event listener: {}", e);
-                // Continue without event listening
+            if let Err(e) = result {
+                error!("Failed to start mpv for video {}: {}", id.to_string(), e);
+                Self::notify_subscribers(&event_subscribers, VideoEvent::Error { id, message: e.to_string() });
+            }
+        });
+        
+        Ok(id)
+    }
+    
+    /// Connects to a freshly spawned mpv process, starts monitoring it and registers the instance
+    fn attach_process(
+        id: VideoId,
+        mut process: Child,
+        socket_path: String,
+        output: Option<OutputCapture>,
+        options: &PlaybackOptions,
+        instances: &Arc<Mutex<HashMap<VideoId, VideoInstance>>>,
+        event_subscribers: &Arc<EventHub>,
+    ) -> Result<VideoId> {
+        let user_data = options.user_data.clone();
+        let pause_target = Arc::new(Mutex::new(None));
+        let client_state = Arc::new(Mutex::new(ClientState::default()));
+        
+        // Fix the IpcConfig, polling events at the same cadence as the playback monitor
+        let poll_interval_ms = options.effective_poll_interval_ms();
+        let ipc_config = if options.connection_timeout_ms.is_some() {
+            IpcConfig::new(
+                options.connection_timeout_ms.unwrap(),
+                poll_interval_ms,
+                true,  // auto_reconnect
+                DEFAULT_MAX_RECONNECT_ATTEMPTS,  // max_reconnect_attempts
+                DEFAULT_RECONNECT_DELAY_MS  // reconnect_delay_ms
+            )
+        } else {
+            IpcConfig {
+                poll_interval_ms,
+                ..IpcConfig::default()
+            }
+        };
+        
+        // Fix the MpvIpcClient connection
+        let ipc_client = match MpvIpcClient::connect_with_config(&socket_path, ipc_config.clone()) {
+            Ok(client) => client,
+            Err(e) => {
+                // If we can't connect, make sure to clean up the process
+                debug!("Failed to connect to mpv IPC socket, killing process: {}", e);
+                let _ = process.kill();
+                let _ = process.wait();
+                return Err(e);
+            }
+        };
+        
+        let ipc_client = Arc::new(Mutex::new(ipc_client));
+        
+        // Fix the MpvIpcClient for events
+        let event_ipc_client = match MpvIpcClient::connect_with_config(&socket_path, ipc_config) {
+            Ok(client) => client,
+            Err(e) => {
+                debug!("Failed to connect event listener to mpv IPC socket: {}", e);
+                // Still return success, but without event listening
                 let instance = VideoInstance {
                     id,
-                    process,
+                    process: Some(process),
                     ipc_client,
                     event_listener: None,
                     event_thread: None,
                     socket_path,
+                    user_data,
+                    instance_tag: options.instance_tag.clone(),
+                    pause_target,
+                    client_state,
+                    pending_controls: Arc::default(),
+                    loop_mode: Arc::default(),
+                    monitor_reset: Arc::default(),
+                    output,
                 };
                 
                 let mut instances = instances.lock().unwrap();
@@ -329,39 +1057,309 @@ impl VideoManager {
                 
                 return Ok(id);
             }
-            
-            // Set up event forwarding
-            let video_id = id;
-            let ipc_client_clone = Arc::clone(&ipc_client);
-            let subscribers_clone = event_subscribers.clone();
-            let interval = options.progress_interval_ms.unwrap_or(1000);
-            
-            // Start event thread
-            let thread = thread::spawn(move || {
-                Self::monitor_playback(video_id, ipc_client_clone, subscribers_clone, interval);
-            });
-            
-            // Store the instance with listener and thread
-            let instance = VideoInstance {
-                id,
-                process,
-                ipc_client,
-                event_listener: Some(listener),
-                event_thread: Some(thread),
-                socket_path,
-            };
-            
-            let mut instances = instances.lock().unwrap();
-            instances.insert(id, instance);
-            
-            Ok(id)
-        }).await.unwrap()
-    }
-    
-    /// Closes a specific video
-    pub async fn close(&self, id: VideoId) -> Result<()> {
-        let instances = self.instances.clone();
-        let subscribers = self.event_subscribers.clone();
+        };
+        
+        // Fix the MpvEventListener creation
+        let mut listener = MpvEventListener::new(event_ipc_client);
+        
+        // Surface mpv's file_error text when a file fails to play
+        let error_subscribers = Arc::clone(event_subscribers);
+        let _ = listener.subscribe("end-file", move |event| {
+            if let MpvEvent::FileEnded { reason: CloseReason::Error, error, .. } = event {
+                let message = error.unwrap_or_else(|| "Playback failed".to_string());
+                debug!("Video {} failed to play: {}", id.to_string(), message);
+                Self::notify_subscribers(&error_subscribers, VideoEvent::Error { id, message });
+            }
+        });
+        
+        // Report when the last playlist entry reaches its end
+        let playlist_client = Arc::clone(&ipc_client);
+        let playlist_subscribers = Arc::clone(event_subscribers);
+        let _ = listener.subscribe("end-file", move |event| {
+            if let MpvEvent::FileEnded { reason: CloseReason::Eof, playlist_entry_id, .. } = event {
+                let playlist = playlist_client.lock().unwrap().get_playlist().unwrap_or_default();
+                if is_last_playlist_entry(&playlist, playlist_entry_id) {
+                    Self::notify_subscribers(&playlist_subscribers, VideoEvent::PlaylistEnded { id });
+                }
+            }
+        });
+        
+        // Re-apply the saved settings when the connection is restored to a restarted mpv
+        let restart_client = Arc::clone(&ipc_client);
+        let restart_state = Arc::clone(&client_state);
+        let last_pid = Mutex::new(ipc_client.lock().unwrap().get_property("pid").ok());
+        let _ = listener.subscribe("connection", move |event| {
+            if let MpvEvent::ConnectionRestored = event {
+                let mut client = restart_client.lock().unwrap();
+                let pid = client.get_property("pid").ok();
+                let mut last_pid = last_pid.lock().unwrap();
+                if pid.is_some() && *last_pid != pid {
+                    debug!("mpv restarted for video {}, re-applying client state", id.to_string());
+                    let state = restart_state.lock().unwrap().clone();
+                    if let Err(e) = state.apply(&mut client) {
+                        error!("Failed to re-apply client state for video {}: {}", id.to_string(), e);
+                    }
+                    *last_pid = pid;
+                }
+            }
+        });
+        
+        // Report resolution changes so layouts can follow adaptive streams
+        let params_subscribers = Arc::clone(event_subscribers);
+        let last_size = Mutex::new(None);
+        let _ = listener.subscribe("video-params", move |event| {
+            if let MpvEvent::PropertyChanged(_, params) = event {
+                let width = params.get("w").and_then(|w| w.as_i64());
+                let height = params.get("h").and_then(|h| h.as_i64());
+                if let (Some(width), Some(height)) = (width, height) {
+                    let mut last_size = last_size.lock().unwrap();
+                    if *last_size != Some((width, height)) {
+                        *last_size = Some((width, height));
+                        Self::notify_subscribers(&params_subscribers, VideoEvent::VideoParamsChanged { id, width, height });
+                    }
+                }
+            }
+        });
+        
+        // Report when a seek has landed, i.e. the first playback-restart after a seek
+        let seek_pending = Arc::new(Mutex::new(false));
+        let seek_started = Arc::clone(&seek_pending);
+        let _ = listener.subscribe("seek", move |event| {
+            if let MpvEvent::SeekStarted = event {
+                *seek_started.lock().unwrap() = true;
+            }
+        });
+        let seek_client = Arc::clone(&ipc_client);
+        let seek_subscribers = Arc::clone(event_subscribers);
+        let _ = listener.subscribe("playback-restart", move |event| {
+            if let MpvEvent::PlaybackRestarted = event {
+                let was_seeking = std::mem::replace(&mut *seek_pending.lock().unwrap(), false);
+                if was_seeking {
+                    let position = seek_client.lock().unwrap().get_time_pos().unwrap_or(0.0);
+                    Self::notify_subscribers(&seek_subscribers, VideoEvent::SeekCompleted { id, position });
+                }
+            }
+        });
+        
+        // Forward script messages sent by the bundled scripts to the application
+        let message_subscribers = Arc::clone(event_subscribers);
+        let _ = listener.subscribe("client-message", move |event| {
+            if let MpvEvent::ClientMessage { args } = event {
+                Self::notify_subscribers(&message_subscribers, VideoEvent::ScriptMessage { id, args });
+            }
+        });
+        
+        // Report metadata tag changes, including ICY titles from internet radio
+        let metadata = Arc::new(Mutex::new(HashMap::new()));
+        let tags = Arc::clone(&metadata);
+        let tags_subscribers = Arc::clone(event_subscribers);
+        let _ = listener.subscribe("metadata", move |event| {
+            if let MpvEvent::PropertyChanged(_, value) = event {
+                let parsed = parse_metadata(&value);
+                let mut tags = tags.lock().unwrap();
+                if *tags != parsed {
+                    *tags = parsed.clone();
+                    Self::notify_subscribers(&tags_subscribers, VideoEvent::MetadataChanged { id, metadata: parsed });
+                }
+            }
+        });
+        let icy_subscribers = Arc::clone(event_subscribers);
+        let _ = listener.subscribe("metadata/by-key/icy-title", move |event| {
+            if let MpvEvent::PropertyChanged(_, serde_json::Value::String(title)) = event {
+                let mut tags = metadata.lock().unwrap();
+                if tags.get("icy-title") != Some(&title) {
+                    tags.insert("icy-title".to_string(), title);
+                    Self::notify_subscribers(&icy_subscribers, VideoEvent::MetadataChanged { id, metadata: tags.clone() });
+                }
+            }
+        });
+        
+        // Forward buffering so frontends can show a spinner while a stream stalls
+        let cache_subscribers = Arc::clone(event_subscribers);
+        let _ = listener.subscribe("cache", move |event| {
+            match event {
+                MpvEvent::Buffering { percent } => {
+                    Self::notify_subscribers(&cache_subscribers, VideoEvent::Buffering { id, percent });
+                }
+                MpvEvent::BufferingDone => {
+                    Self::notify_subscribers(&cache_subscribers, VideoEvent::BufferingDone { id });
+                }
+                _ => {}
+            }
+        });
+        
+        // Start the listener
+        if let Err(e) = listener.start_listening() {
+            debug!("Failed to start event listener: {}", e);
+            // Continue without event listening
+            let instance = VideoInstance {
+                id,
+                process: Some(process),
+                ipc_client,
+                event_listener: None,
+                event_thread: None,
+                socket_path,
+                user_data,
+                instance_tag: options.instance_tag.clone(),
+                pause_target,
+                client_state,
+                pending_controls: Arc::default(),
+                loop_mode: Arc::default(),
+                monitor_reset: Arc::default(),
+                output,
+            };
+            
+            let mut instances = instances.lock().unwrap();
+            instances.insert(id, instance);
+            
+            return Ok(id);
+        }
+        
+        // Set up event forwarding
+        let video_id = id;
+        let ipc_client_clone = Arc::clone(&ipc_client);
+        let subscribers_clone = Arc::clone(event_subscribers);
+        let settings = MonitorSettings {
+            interval_ms: poll_interval_ms,
+            keep_open: options.keep_open,
+            observe: options.observe,
+            prebuffer_secs: options.prebuffer_secs,
+        };
+        let loop_mode = Arc::new(Mutex::new(LoopMode::Off));
+        let monitor_reset = Arc::new(Mutex::new(false));
+        let controls = MonitorControls {
+            pause_target: Arc::clone(&pause_target),
+            loop_mode: Arc::clone(&loop_mode),
+            reset: Arc::clone(&monitor_reset),
+        };
+        
+        // Start event thread
+        let thread = thread::spawn(move || {
+            Self::monitor_playback(
+                video_id,
+                ipc_client_clone,
+                subscribers_clone,
+                settings,
+                controls,
+            );
+        });
+        
+        // Store the instance with listener and thread
+        let instance = VideoInstance {
+            id,
+            process: Some(process),
+            ipc_client,
+            event_listener: Some(listener),
+            event_thread: Some(thread),
+            socket_path,
+            user_data,
+            instance_tag: options.instance_tag.clone(),
+            pause_target,
+            client_state,
+            pending_controls: Arc::default(),
+            loop_mode,
+            monitor_reset,
+            output,
+        };
+        
+        let mut instances = instances.lock().unwrap();
+        instances.insert(id, instance);
+        
+        Ok(id)
+    }
+    
+    /// Registers an already running mpv process with the manager and starts monitoring it
+    pub(crate) async fn adopt(&self, process: Child, socket_path: String, options: PlaybackOptions) -> Result<VideoId> {
+        let instances = Arc::clone(&self.instances);
+        let event_subscribers = self.event_subscribers.clone();
+        let id = VideoId::new();
+        
+        tokio::task::spawn_blocking(move || {
+            Self::attach_process(id, process, socket_path, None, &options, &instances, &event_subscribers)
+        }).await.unwrap()
+    }
+    
+    /// Removes a video from the manager without terminating its mpv process.
+    /// Returns the process and its socket path so the caller can take ownership.
+    pub(crate) async fn detach(&self, id: VideoId) -> Option<(Child, String)> {
+        let instances = self.instances.clone();
+        let subscribers = self.event_subscribers.clone();
+        
+        tokio::task::spawn_blocking(move || {
+            let mut instance = instances.lock().unwrap().remove(&id)?;
+            debug!("Detaching video with ID: {}", id.to_string());
+            
+            let process = Self::release_instance(&mut instance);
+            subscribers.forget(id);
+            
+            Some((process?, instance.socket_path.clone()))
+        }).await.unwrap_or(None)
+    }
+    
+    /// Waits for a video's mpv process to exit and resolves with its exit code.
+    /// Resolves with `None` if the video is closed or detached through the manager first,
+    /// or if mpv was terminated by a signal. Dropping the future cancels the wait.
+    pub fn wait_for_exit(&self, id: VideoId) -> impl Future<Output = Option<i32>> + Send + 'static {
+        let instances = Arc::clone(&self.instances);
+        
+        async move {
+            loop {
+                {
+                    let mut instances = instances.lock().unwrap();
+                    let process = instances.get_mut(&id).and_then(|instance| instance.process.as_mut())?;
+                    
+                    match process.try_wait() {
+                        Ok(Some(status)) => {
+                            debug!("mpv for video {} exited with {}", id.to_string(), status);
+                            return status.code();
+                        },
+                        Ok(None) => {},
+                        Err(e) => {
+                            error!("Failed to check mpv process status for video {}: {}", id.to_string(), e);
+                            return None;
+                        }
+                    }
+                }
+                
+                tokio::time::sleep(Duration::from_millis(EXIT_POLL_INTERVAL_MS)).await;
+            }
+        }
+    }
+    
+    /// Stops monitoring an instance and closes its IPC connection, leaving mpv itself running.
+    /// Returns the process handle so the caller decides what happens to it.
+    fn release_instance(instance: &mut VideoInstance) -> Option<Child> {
+        if let Ok(mut client) = instance.ipc_client.lock() {
+            client.mark_as_intentionally_closed();
+        }
+        
+        if let Some(mut listener) = instance.event_listener.take() {
+            let _ = listener.stop_listening();
+            let _ = listener.handle_process_exit();
+        }
+        
+        if let Ok(mut client) = instance.ipc_client.lock() {
+            client.close();
+        }
+        
+        if let Some(thread) = instance.event_thread.take() {
+            let _ = thread.join();
+        }
+        
+        instance.process.take()
+    }
+    
+    /// Closes a specific video
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(video_id = %id.to_string())))]
+    pub async fn close(&self, id: VideoId) -> Result<()> {
+        self.close_graceful(id, Duration::from_millis(100)).await
+    }
+    
+    /// Closes a specific video, giving mpv up to `timeout` to exit before killing it
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(video_id = %id.to_string())))]
+    pub async fn close_graceful(&self, id: VideoId, timeout: Duration) -> Result<()> {
+        let instances = self.instances.clone();
+        let subscribers = self.event_subscribers.clone();
         
         // Spawn a blocking task to close the video
         match tokio::task::spawn_blocking(move || {
@@ -394,13 +1392,26 @@ impl VideoManager {
                     client.close();
                 }
                 
-                // Wait briefly for mpv to process the quit command
-                use std::thread::sleep;
-                use std::time::Duration;
-                sleep(Duration::from_millis(100));
-                
-                // Kill the process if it's still running
-                let _ = instance.process.kill();
+                // Give mpv time to process the quit command and exit on its own
+                if let Some(process) = instance.process.as_mut() {
+                    let deadline = std::time::Instant::now() + timeout;
+                    
+                    loop {
+                        match process.try_wait() {
+                            Ok(Some(_)) => break,
+                            Ok(None) if std::time::Instant::now() < deadline => {
+                                thread::sleep(Duration::from_millis(EXIT_POLL_INTERVAL_MS));
+                            }
+                            _ => {
+                                // Kill the process if it's still running
+                                debug!("mpv did not exit in time for video {}, killing it", id.to_string());
+                                let _ = process.kill();
+                                let _ = process.wait();
+                                break;
+                            }
+                        }
+                    }
+                }
                 
                 // Wait for any event thread to complete
                 if let Some(thread) = instance.event_thread.take() {
@@ -411,6 +1422,9 @@ impl VideoManager {
                 // Notify subscribers that the video was closed
                 Self::notify_subscribers(&subscribers, VideoEvent::Closed { id });
                 
+                // The monitor thread has been joined, so no further events can arrive for this id
+                subscribers.forget(id);
+                
                 debug!("Video {} closed successfully", id.to_string());
             }
             
@@ -465,8 +1479,11 @@ impl VideoManager {
                     use std::time::Duration;
                     sleep(Duration::from_millis(100));
                     
-                    // Kill the process if it's still running
-                    let _ = instance.process.kill();
+                    // Kill the process if it's still running, and reap it
+                    if let Some(process) = instance.process.as_mut() {
+                        let _ = process.kill();
+                        let _ = process.wait();
+                    }
                     
                     // Join the event thread if it exists
                     if let Some(thread) = instance.event_thread.take() {
@@ -476,6 +1493,7 @@ impl VideoManager {
                     
                     // Notify subscribers that the video was closed
                     Self::notify_subscribers(&subscribers, VideoEvent::Closed { id });
+                    subscribers.forget(id);
                     
                     debug!("Video {} closed successfully", id.to_string());
                 }
@@ -485,23 +1503,62 @@ impl VideoManager {
         }).await.unwrap()
     }
     
+    /// Closes every video from synchronous code, such as an app exit handler, by blocking on
+    /// `runtime`. Fails when called from inside a runtime, where `close_all` should be awaited instead.
+    pub fn shutdown(&self, runtime: &tokio::runtime::Runtime) -> Result<()> {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Err(Error::MpvError(
+                "shutdown cannot be called from within a tokio runtime, use close_all instead".to_string()
+            ));
+        }
+        
+        runtime.block_on(self.close_all())
+    }
+    
+    /// Locks a manager shared behind an async mutex and shuts it down on `runtime`, like `shutdown`.
+    /// Gives up after a short wait if another task (e.g. a starting video) holds the lock.
+    pub fn shutdown_shared(manager: &tokio::sync::Mutex<Self>, runtime: &tokio::runtime::Runtime) -> Result<()> {
+        // Checked before blocking, as block_on panics inside a runtime
+        if tokio::runtime::Handle::try_current().is_ok() {
+            return Err(Error::MpvError(
+                "shutdown cannot be called from within a tokio runtime, use close_all instead".to_string()
+            ));
+        }
+        
+        let timeout = Duration::from_millis(SHUTDOWN_LOCK_TIMEOUT_MS);
+        let manager = runtime
+            .block_on(async { tokio::time::timeout(timeout, manager.lock()).await })
+            .map_err(|_| Error::MpvError("Timed out waiting for the video manager".to_string()))?;
+        
+        manager.shutdown(runtime)
+    }
+    
     /// Subscribes to video events
     pub async fn subscribe(&self) -> EventSubscription {
         let event_subscribers = self.event_subscribers.clone();
-        let (sender, receiver) = mpsc::channel(100);
+        let (terminal_sender, terminal) = mpsc::unbounded_channel();
         let id = Uuid::new_v4();
         
+        // Hold the sequence lock so both channels start at the same event
+        let _next_seq = event_subscribers.next_seq.lock().unwrap();
+        let receiver = event_subscribers.sender.subscribe();
+        
         // Add the subscriber
         let subscriber = EventSubscriber {
             id,
-            sender,
+            terminal_sender,
         };
         
-        let mut subscribers = event_subscribers.lock().unwrap();
+        let mut subscribers = event_subscribers.subscribers.lock().unwrap();
         subscribers.push(subscriber);
         
         EventSubscription {
             receiver,
+            terminal,
+            terminal_backlog: VecDeque::new(),
+            pending: VecDeque::new(),
+            last_seq: None,
+            lagged: 0,
             _id: id,
         }
     }
@@ -511,13 +1568,13 @@ impl VideoManager {
         let event_subscribers = self.event_subscribers.clone();
         
         tokio::task::spawn_blocking(move || {
-            let mut subscribers = event_subscribers.lock().unwrap();
+            let mut subscribers = event_subscribers.subscribers.lock().unwrap();
             subscribers.retain(|s| s.id != subscription_id);
         }).await.unwrap();
     }
     
     /// Notifies subscribers of an event
-    fn notify_subscribers(subscribers: &Arc<Mutex<Vec<EventSubscriber>>>, event: VideoEvent) {
+    fn notify_subscribers(subscribers: &Arc<EventHub>, event: VideoEvent) {
         // Get event type and video ID based on the enum variant
         let (event_type, video_id) = match &event {
             VideoEvent::Progress { id, .. } => ("progress", id),
@@ -526,39 +1583,72 @@ impl VideoManager {
             VideoEvent::Resumed { id } => ("resumed", id),
             VideoEvent::Ended { id } => ("ended", id),
             VideoEvent::Closed { id } => ("closed", id),
+            VideoEvent::PlaylistEnded { id } => ("playlist-ended", id),
             VideoEvent::Error { id, .. } => ("error", id),
+            VideoEvent::VideoParamsChanged { id, .. } => ("video-params-changed", id),
+            VideoEvent::SeekCompleted { id, .. } => ("seek-completed", id),
+            VideoEvent::ScriptMessage { id, .. } => ("script-message", id),
+            VideoEvent::MetadataChanged { id, .. } => ("metadata-changed", id),
+            VideoEvent::Buffering { id, .. } => ("buffering", id),
+            VideoEvent::BufferingDone { id } => ("buffering-done", id),
         };
-
-        // Check for "closed" or "ended" events to prevent duplicates using process-wide cache
+        
+        // Check for "closed" or "ended" events to prevent duplicates using the manager-wide cache
         if event_type == "closed" || event_type == "ended" {
-            let should_skip = {
-                let mut events = NOTIFIED_EVENTS.lock().unwrap();
-                let video_events = events.entry(video_id.0.to_string()).or_insert_with(HashSet::new);
-                if video_events.contains(event_type) {
-                    debug!("Skipping duplicate {} notification for video {:?} (process-wide deduplication)", 
-                          event_type, video_id);
-                    true
-                } else {
-                    video_events.insert(event_type.to_string());
-                    debug!("Sending first {} notification for video {:?}", event_type, video_id);
-                    false
-                }
-            };
-
-            if should_skip {
+            if !subscribers.mark_notified(*video_id, event_type) {
+                debug!("Skipping duplicate {} notification for video {:?}", event_type, video_id);
                 return;
             }
+            debug!("Sending first {} notification for video {:?}", event_type, video_id);
         }
-
-        // Get the subscribers and notify them
-        if let Ok(subscribers) = subscribers.lock() {
-            // Notify all subscribers of the event
-            for subscriber in subscribers.iter() {
-                // Use try_send to avoid blocking
-                if let Err(e) = subscriber.sender.try_send(event.clone()) {
-                    debug!("Failed to notify subscriber: {}", e);
-                }
+        
+        let mut next_seq = subscribers.next_seq.lock().unwrap();
+        let seq = *next_seq;
+        *next_seq += 1;
+        
+        // Terminal events also go through each subscriber's unbounded channel, so a
+        // lagging subscriber can never miss them; subscribers that went away are dropped
+        if matches!(event_type, "closed" | "ended" | "error") {
+            if let Ok(mut terminal_subscribers) = subscribers.subscribers.lock() {
+                terminal_subscribers.retain(|subscriber| {
+                    subscriber.terminal_sender.send((seq, event.clone())).is_ok()
+                });
+            }
+        }
+        
+        // Sending only fails when nobody is subscribed
+        let _ = subscribers.sender.send((seq, event));
+    }
+    
+    /// Waits until mpv has cached `threshold` seconds ahead (or a timeout passes),
+    /// then restores the window and starts playback
+    fn wait_for_prebuffer(id: VideoId, ipc_client: &Arc<Mutex<MpvIpcClient>>, threshold: f64) {
+        let deadline = std::time::Instant::now() + Duration::from_millis(PREBUFFER_TIMEOUT_MS);
+        
+        loop {
+            let cached = ipc_client.lock().unwrap()
+                .get_property("demuxer-cache-time")
+                .ok()
+                .and_then(|value| value.as_f64());
+            
+            if cached.is_some_and(|cached| cached >= threshold) {
+                debug!("Pre-buffered {:?}s for video {}", cached, id.to_string());
+                break;
+            }
+            if std::time::Instant::now() >= deadline {
+                debug!("Pre-buffering timed out for video {} with {:?}s cached", id.to_string(), cached);
+                break;
             }
+            
+            thread::sleep(Duration::from_millis(PREBUFFER_POLL_INTERVAL_MS));
+        }
+        
+        let mut client = ipc_client.lock().unwrap();
+        if let Err(e) = client.set_properties(&[
+            ("window-minimized", serde_json::json!(false)),
+            ("pause", serde_json::json!(false)),
+        ]) {
+            error!("Failed to reveal pre-buffered video {}: {}", id.to_string(), e);
         }
     }
     
@@ -566,17 +1656,34 @@ impl VideoManager {
     fn monitor_playback(
         id: VideoId,
         ipc_client: Arc<Mutex<MpvIpcClient>>,
-        subscribers: Arc<Mutex<Vec<EventSubscriber>>>,
-        interval_ms: u64,
+        subscribers: Arc<EventHub>,
+        settings: MonitorSettings,
+        controls: MonitorControls,
     ) {
         use std::time::Duration;
         
+        let MonitorSettings { interval_ms, keep_open, observe, prebuffer_secs } = settings;
+        let MonitorControls { pause_target, loop_mode, reset } = controls;
+        
+        // Hold the minimized, paused window back until enough of the stream is cached
+        if let Some(threshold) = prebuffer_secs {
+            Self::wait_for_prebuffer(id, &ipc_client, threshold);
+        }
+        
         // Send started event
         Self::notify_subscribers(&subscribers, VideoEvent::Started { id });
         
+        // Without an explicit setting, follow whatever the mpv config resolved keep-open to
+        let keep_open = keep_open.unwrap_or_else(|| {
+            ipc_client.lock().unwrap().get_property("keep-open").ok()
+                .and_then(|value| KeepOpen::from_mpv_value(&value))
+                .unwrap_or(KeepOpen::No)
+        });
+        
         let interval = Duration::from_millis(interval_ms);
         let mut last_position = -1.0;
         let mut last_paused = false;
+        let mut eof_notified = false;
         let mut consecutive_errors = 0;
         let mut last_playback_status = String::new();  // Track previous playback status for changes
         let max_consecutive_errors = 3;  // Maximum number of consecutive errors before considering the player closed
@@ -585,6 +1692,14 @@ impl VideoManager {
             // Sleep for the specified interval
             thread::sleep(interval);
             
+            // A new file was loaded into this mpv, so track it from scratch
+            if std::mem::take(&mut *reset.lock().unwrap()) {
+                debug!("New file loaded for video {}, resetting playback tracking", id.to_string());
+                last_position = -1.0;
+                last_playback_status.clear();
+                eof_notified = false;
+            }
+            
             // First check if we are intentionally closed already
             let is_intentionally_closed = if let Ok(client) = ipc_client.lock() {
                 client.is_intentionally_closed()
@@ -601,7 +1716,9 @@ impl VideoManager {
             
             // Check if the ipc client is connected and socket exists
             // This is more reliable than just checking is_running
-            let socket_exists = if let Ok(mut client) = ipc_client.lock() {
+            let socket_exists = if !observe.lifecycle {
+                true
+            } else if let Ok(mut client) = ipc_client.lock() {
                 match client.get_property("pid") {
                     Ok(_) => {
                         // Successfully communicated, reset error counter
@@ -643,7 +1760,9 @@ impl VideoManager {
             }
             
             // Check current playback status - useful for detecting OSC-triggered actions
-            let current_status = if let Ok(mut client) = ipc_client.lock() {
+            let current_status = if !observe.lifecycle {
+                String::new()
+            } else if let Ok(mut client) = ipc_client.lock() {
                 match client.get_playback_status() {
                     Ok(status) => status,
                     Err(_) => String::new()
@@ -672,58 +1791,57 @@ impl VideoManager {
                 last_playback_status = current_status;
             }
             
-            // Get current playback position
-            let position = if let Ok(mut client) = ipc_client.lock() {
-                if let Ok(value) = client.get_property("time-pos") {
-                    value.as_f64()
-                } else {
-                    None
-                }
-            } else {
-                None
-            };
+            // Query every observed playback property in a single pipelined round trip
+            let needs_position = observe.progress || pause_target.lock().unwrap().is_some();
+            let mut properties = Vec::new();
+            if needs_position {
+                properties.push("time-pos");
+            }
+            if observe.progress {
+                properties.push("duration");
+            }
+            if observe.pause_state {
+                properties.push("pause");
+            }
+            if observe.lifecycle {
+                // idle-active indicates mpv is waiting for commands
+                properties.extend(["eof-reached", "idle-active"]);
+            }
             
-            let duration = if let Ok(mut client) = ipc_client.lock() {
-                if let Ok(value) = client.get_property("duration") {
-                    value.as_f64()
-                } else {
-                    None
-                }
+            let values = if properties.is_empty() {
+                HashMap::new()
+            } else if let Ok(mut client) = ipc_client.lock() {
+                client.get_properties(&properties).unwrap_or_default()
             } else {
-                None
+                HashMap::new()
             };
             
-            let paused = if let Ok(mut client) = ipc_client.lock() {
-                if let Ok(value) = client.get_property("pause") {
-                    value.as_bool().unwrap_or(false)
-                } else {
-                    false
-                }
+            let position = values.get("time-pos").and_then(|v| v.as_f64());
+            let duration = values.get("duration").and_then(|v| v.as_f64());
+            let paused = if observe.pause_state {
+                values.get("pause").and_then(|v| v.as_bool()).unwrap_or(false)
             } else {
-                false
+                last_paused
             };
             
             // Check if playback has ended
-            let eof = if let Ok(mut client) = ipc_client.lock() {
-                if let Ok(value) = client.get_property("eof-reached") {
-                    value.as_bool().unwrap_or(false)
-                } else {
-                    false
-                }
-            } else {
-                false
-            };
+            let eof = values.get("eof-reached").and_then(|v| v.as_bool()).unwrap_or(false);
+            let idle_active = values.get("idle-active").and_then(|v| v.as_bool()).unwrap_or(false);
             
-            // Additionally check for idle-active which indicates mpv is waiting for commands
-            let idle_active = if let Ok(mut client) = ipc_client.lock() {
-                if let Ok(value) = client.get_property("idle-active") {
-                    value.as_bool().unwrap_or(false)
-                } else {
-                    false
+            // Pause once the position crosses a pending pause-at target
+            let target = *pause_target.lock().unwrap();
+            if let (Some(target), Some(position)) = (target, position) {
+                if !paused && position >= target {
+                    debug!("Reached pause-at target {} for video {}", target, id.to_string());
+                    if let Ok(mut client) = ipc_client.lock() {
+                        if client.set_pause(true).is_ok() {
+                            *pause_target.lock().unwrap() = None;
+                            Self::notify_subscribers(&subscribers, VideoEvent::Paused { id });
+                            last_paused = true;
+                        }
+                    }
                 }
-            } else {
-                false
-            };
+            }
             
             // Send pause/resume events
             if paused != last_paused {
@@ -755,6 +1873,23 @@ impl VideoManager {
                 }
             }
             
+            // While looping, mpv starts over by itself, so EOF is not the end of playback
+            if eof && *loop_mode.lock().unwrap() != LoopMode::Off {
+                debug!("EOF reached for video {} while looping, continuing", id.to_string());
+                continue;
+            }
+            
+            // With keep-open, mpv stays on the last frame, so report the end but keep monitoring
+            if eof && keep_open != KeepOpen::No {
+                if !eof_notified {
+                    debug!("EOF reached for video {} (keep-open active)", id.to_string());
+                    Self::notify_subscribers(&subscribers, VideoEvent::Ended { id });
+                    eof_notified = true;
+                }
+                continue;
+            }
+            eof_notified = false;
+            
             // Check if playback has ended
             if eof {
                 debug!("EOF reached for video {}", id.to_string());
@@ -789,7 +1924,8 @@ impl VideoManager {
         }
     }
     
-    /// Updates window properties for a video instance
+    /// Updates window properties for a video instance in a single pipelined batch.
+    /// Every property is attempted; the error lists each one mpv rejected.
     pub async fn update_window(&self, id: VideoId, window: WindowOptions) -> Result<()> {
         let instances = self.instances.clone();
         
@@ -799,37 +1935,77 @@ impl VideoManager {
             if let Some(instance) = instances.get(&id) {
                 let mut ipc_client = instance.ipc_client.lock().unwrap();
                 
-                // Apply window properties one by one
+                // Collect the window properties so they are sent together
+                let mut properties = Vec::new();
+                
                 if let Some((x, y)) = window.position {
-                    let pos_value = serde_json::json!(format!("{}+{}", x, y));
-                    ipc_client.set_property("window-pos", pos_value)?;
+                    properties.push(("window-pos", serde_json::json!(format!("{}+{}", x, y))));
                 }
                 
                 if let Some((width, height)) = window.size {
-                    let size_value = serde_json::json!(format!("{}x{}", width, height));
-                    ipc_client.set_property("geometry", size_value)?;
+                    properties.push(("geometry", serde_json::json!(format!("{}x{}", width, height))));
                 }
                 
-                if window.always_on_top {
-                    ipc_client.set_property("ontop", serde_json::json!(true))?;
+                if let Some(always_on_top) = window.always_on_top {
+                    properties.push(("ontop", serde_json::json!(always_on_top)));
                 }
                 
                 if let Some(opacity) = window.opacity {
                     let opacity = opacity.max(0.0).min(1.0);
-                    ipc_client.set_property("alpha", serde_json::json!(opacity))?;
+                    properties.push(("alpha", serde_json::json!(opacity)));
                 }
                 
-                if window.start_hidden {
-                    ipc_client.set_property("window-minimized", serde_json::json!(true))?;
+                if window.start_hidden == Some(true) {
+                    properties.push(("window-minimized", serde_json::json!(true)));
                 }
                 
-                Ok(())
+                // Every property is attempted; report all of the ones that failed
+                let failures = ipc_client.set_properties_collect(&properties)?;
+                if failures.is_empty() {
+                    Ok(())
+                } else {
+                    let failed: Vec<String> = failures.iter()
+                        .map(|(property, e)| format!("{} ({})", property, e))
+                        .collect();
+                    Err(crate::Error::MpvError(format!("Failed to update window properties: {}", failed.join(", "))))
+                }
             } else {
                 Err(crate::Error::MpvError(format!("Video instance not found: {}", id.to_string())))
             }
         }).await.unwrap()
     }
     
+    /// Returns the IDs of the videos started with the given `instance_tag`
+    pub fn get_instances_by_tag(&self, tag: &str) -> Vec<VideoId> {
+        let instances = self.instances.lock().unwrap();
+        instances.values()
+            .filter(|instance| instance.instance_tag.as_deref() == Some(tag))
+            .map(|instance| instance.id)
+            .collect()
+    }
+    
+    /// Returns whether the video's mpv process is still running, from the OS process status.
+    /// Unlike IPC queries this is cheap and unaffected by socket hiccups, so it suits UI heartbeats.
+    /// Unknown and detached videos are reported as not alive.
+    pub fn is_alive(&self, id: VideoId) -> bool {
+        let mut instances = self.instances.lock().unwrap();
+        
+        match instances.get_mut(&id).and_then(|instance| instance.process.as_mut()) {
+            Some(process) => matches!(process.try_wait(), Ok(None)),
+            None => false,
+        }
+    }
+    
+    /// Gets the application data associated with a video at play time
+    pub fn get_user_data(&self, id: VideoId) -> Result<Option<serde_json::Value>> {
+        let instances = self.instances.lock().unwrap();
+        
+        match instances.get(&id) {
+            Some(instance) => Ok(instance.user_data.clone()),
+            None => Err(self.missing_instance(id)),
+        }
+    }
+    
     /// Gets the current playback progress for a video
     pub async fn get_progress(&self, id: VideoId) -> Result<PlaybackProgress> {
         let instances = self.instances.lock().unwrap();
@@ -891,82 +2067,849 @@ impl VideoManager {
             
             Ok(())
         } else {
-            Err(Error::MpvError(format!("Video instance not found: {}", id.to_string())))
+            Err(self.missing_instance(id))
         }
     }
     
-    /// Resumes video playback
-    pub async fn resume(&self, id: VideoId) -> Result<()> {
+    /// Pauses playback automatically once the position reaches `seconds`.
+    /// Pauses immediately if playback is already past that point.
+    pub async fn pause_at(&self, id: VideoId, seconds: f64) -> Result<()> {
         let instances = self.instances.lock().unwrap();
         
         if let Some(instance) = instances.get(&id) {
             let client = instance.ipc_client.clone();
             let mut client_guard = client.lock().unwrap();
             
-            client_guard.set_pause(false)?;
+            let position = client_guard.get_property("time-pos")?.as_f64().unwrap_or(0.0);
+            if position >= seconds {
+                client_guard.set_pause(true)?;
+                *instance.pause_target.lock().unwrap() = None;
+                
+                // Notify subscribers
+                Self::notify_subscribers(
+                    &self.event_subscribers,
+                    VideoEvent::Paused { id }
+                );
+            } else {
+                *instance.pause_target.lock().unwrap() = Some(seconds);
+            }
             
-            // Notify subscribers
-            Self::notify_subscribers(
-                &self.event_subscribers,
-                VideoEvent::Resumed { id }
-            );
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Replaces the IPC configuration of a live video's connections without reconnecting,
+    /// e.g. to switch to more aggressive reconnection when the network changes
+    pub async fn update_ipc_config(&self, id: VideoId, config: IpcConfig) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            instance.ipc_client.lock().unwrap().update_config(config.clone());
+            if let Some(listener) = &instance.event_listener {
+                listener.update_ipc_config(config);
+            }
             
             Ok(())
         } else {
-            Err(Error::MpvError(format!("Video instance not found: {}", id.to_string())))
+            Err(self.missing_instance(id))
         }
     }
     
-    /// Seeks to a specific position in the video
-    pub async fn seek(&self, id: VideoId, position: f64) -> Result<()> {
+    /// Sets the audio output channel layout, e.g. to downmix surround sound to stereo
+    pub async fn set_audio_channels(&self, id: VideoId, layout: AudioChannels) -> Result<()> {
         let instances = self.instances.lock().unwrap();
         
         if let Some(instance) = instances.get(&id) {
             let client = instance.ipc_client.clone();
             let mut client_guard = client.lock().unwrap();
             
-            client_guard.seek(position)?;
+            client_guard.set_audio_channels(layout)?;
             
             Ok(())
         } else {
-            Err(Error::MpvError(format!("Video instance not found: {}", id.to_string())))
+            Err(self.missing_instance(id))
         }
     }
     
-    /// Sets the volume for a video
-    pub async fn set_volume(&self, id: VideoId, volume: i32) -> Result<()> {
+    /// Gets the audio output channel layout
+    pub async fn get_audio_channels(&self, id: VideoId) -> Result<AudioChannels> {
         let instances = self.instances.lock().unwrap();
         
         if let Some(instance) = instances.get(&id) {
             let client = instance.ipc_client.clone();
             let mut client_guard = client.lock().unwrap();
             
-            client_guard.set_volume(volume as f64)?;
+            client_guard.get_audio_channels()
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Gets the last lines mpv printed to stdout/stderr, oldest first.
+    /// Only available for videos started with `capture_output`.
+    pub async fn get_output(&self, id: VideoId) -> Result<Vec<String>> {
+        let instances = self.instances.lock().unwrap();
+        
+        match instances.get(&id) {
+            Some(instance) => instance.output.as_ref()
+                .map(OutputCapture::tail)
+                .ok_or_else(|| Error::ConfigError("Output capture is not enabled for this video".to_string())),
+            None => Err(self.missing_instance(id)),
+        }
+    }
+    
+    /// Gets whether the video is playing, paused, buffering or idle
+    pub async fn playback_state(&self, id: VideoId) -> Result<PlaybackState> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
             
-            Ok(())
+            client_guard.playback_state()
         } else {
-            Err(Error::MpvError(format!("Video instance not found: {}", id.to_string())))
+            Err(self.missing_instance(id))
         }
     }
     
-    /// Gets detailed information about a video
-    pub async fn get_video_info(&self, id: VideoId) -> Result<VideoInfo> {
+    /// Gets the demuxer cache statistics of the video, all 0 when it has no cache
+    pub async fn get_cache_stats(&self, id: VideoId) -> Result<CacheStats> {
         let instances = self.instances.lock().unwrap();
         
         if let Some(instance) = instances.get(&id) {
             let client = instance.ipc_client.clone();
             let mut client_guard = client.lock().unwrap();
             
-            // Get position
-            let position = match client_guard.get_property("playback-time") {
-                Ok(pos) => pos.as_f64().unwrap_or(0.0),
-                Err(_) => 0.0,
-            };
+            client_guard.get_cache_stats()
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Gets the version string of the mpv binary playing the video, e.g. `mpv 0.38.0`
+    pub async fn get_mpv_version(&self, id: VideoId) -> Result<String> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
             
-            // Get duration
-            let duration = match client_guard.get_property("duration") {
-                Ok(dur) => dur.as_f64().unwrap_or(0.0),
-                Err(_) => 0.0,
+            client_guard.get_mpv_version_string()
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Loads a file or URL into the video's existing mpv window, replacing the current
+    /// file or appending it to the playlist
+    pub async fn load_file(&self, id: VideoId, source: String, mode: LoadMode) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.load_file(&source, mode)?;
+            if mode != LoadMode::Append {
+                *instance.monitor_reset.lock().unwrap() = true;
+            }
+            
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Sets whether the current file or the whole playlist repeats
+    pub async fn set_loop(&self, id: VideoId, mode: LoopMode) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.set_loop(mode)?;
+            *instance.loop_mode.lock().unwrap() = mode;
+            
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Clears a pending pause set via `pause_at`
+    pub async fn clear_pause_at(&self, id: VideoId) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            *instance.pause_target.lock().unwrap() = None;
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Resumes video playback
+    pub async fn resume(&self, id: VideoId) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.set_pause(false)?;
+            
+            // Notify subscribers
+            Self::notify_subscribers(
+                &self.event_subscribers,
+                VideoEvent::Resumed { id }
+            );
+            
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Seeks to a specific position in the video
+    pub async fn seek(&self, id: VideoId, position: f64) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            if let Some(window) = self.command_debounce {
+                Self::queue_control(instance, window, |pending| pending.seek = Some(position));
+                return Ok(());
+            }
+            
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.seek(position)?;
+            
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Seeks to exactly `position` seconds rather than the nearest keyframe.
+    /// Slower than `seek`, and never debounced, as it is meant for frame-accurate scrubbing.
+    pub async fn seek_exact(&self, id: VideoId, position: f64) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.seek_with_mode(position, SeekMode::Exact)?;
+            
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Captures `count` evenly spaced video frames into `dir` and returns the file paths.
+    /// Files shorter than `count` seconds get one thumbnail per second of video instead.
+    /// The original position and pause state are restored afterwards.
+    pub async fn generate_thumbnails(&self, id: VideoId, count: usize, dir: PathBuf) -> Result<Vec<PathBuf>> {
+        use std::time::{Duration, Instant};
+        
+        let client = {
+            let instances = self.instances.lock().unwrap();
+            match instances.get(&id) {
+                Some(instance) => instance.ipc_client.clone(),
+                None => return Err(self.missing_instance(id)),
+            }
+        };
+        
+        std::fs::create_dir_all(&dir).map_err(|e| Error::Io(e.to_string()))?;
+        
+        tokio::task::spawn_blocking(move || {
+            let mut client = client.lock().unwrap();
+            
+            let duration = client.get_duration()?;
+            let original_position = client.get_time_pos().unwrap_or(0.0);
+            let was_paused = client.get_pause().unwrap_or(false);
+            
+            let count = count.min((duration.floor() as usize).max(1));
+            
+            client.set_pause(true)?;
+            
+            let mut paths = Vec::with_capacity(count);
+            let mut result = Ok(());
+            for index in 0..count {
+                let position = duration * (index + 1) as f64 / (count + 1) as f64;
+                let path = dir.join(format!("thumbnail_{:03}.png", index + 1));
+                
+                if let Err(e) = client.command("seek", &[serde_json::json!(position), serde_json::json!("absolute+exact")]) {
+                    result = Err(e);
+                    break;
+                }
+                
+                // Wait for the seek to land so the screenshot shows the new frame
+                let started = Instant::now();
+                while client.get_property("seeking").ok().and_then(|v| v.as_bool()).unwrap_or(false)
+                    && started.elapsed() < Duration::from_secs(5) {
+                    thread::sleep(Duration::from_millis(20));
+                }
+                
+                match client.screenshot_to_file(&path.to_string_lossy(), false) {
+                    Ok(_) => paths.push(path),
+                    Err(e) => {
+                        result = Err(e);
+                        break;
+                    }
+                }
+            }
+            
+            // Restore the original playback state even if a capture failed
+            let _ = client.command("seek", &[serde_json::json!(original_position), serde_json::json!("absolute+exact")]);
+            let _ = client.set_pause(was_paused);
+            
+            result.map(|_| paths)
+        }).await.unwrap()
+    }
+    
+    /// Sets the playback speed; zero or negative speeds are clamped to a small positive minimum
+    pub async fn set_speed(&self, id: VideoId, speed: f64) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            let speed = speed.max(MIN_PLAYBACK_SPEED);
+            client_guard.set_speed(speed)?;
+            instance.client_state.lock().unwrap().speed = Some(speed);
+            
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Gets the playback speed
+    pub async fn get_speed(&self, id: VideoId) -> Result<f64> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.get_speed()
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Gets the ID of the currently selected audio track, or `None` if audio is off
+    pub async fn get_current_audio_track(&self, id: VideoId) -> Result<Option<i64>> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.get_current_audio_track()
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Gets the ID of the currently selected subtitle track, or `None` if subtitles are off
+    pub async fn get_current_subtitle_track(&self, id: VideoId) -> Result<Option<i64>> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.get_current_subtitle_track()
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Lists the audio tracks of the current file
+    pub async fn get_audio_tracks(&self, id: VideoId) -> Result<Vec<TrackInfo>> {
+        self.get_tracks_of_type(id, "audio")
+    }
+    
+    /// Lists the subtitle tracks of the current file
+    pub async fn get_subtitle_tracks(&self, id: VideoId) -> Result<Vec<TrackInfo>> {
+        self.get_tracks_of_type(id, "sub")
+    }
+    
+    /// Reads mpv's `track-list`, keeping only tracks of the given type
+    fn get_tracks_of_type(&self, id: VideoId, track_type: &str) -> Result<Vec<TrackInfo>> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            let tracks = match client_guard.get_property("track-list")? {
+                serde_json::Value::Array(tracks) => tracks,
+                _ => return Err(Error::MpvError("Invalid track-list type".to_string())),
+            };
+            
+            Ok(tracks.iter()
+                .filter_map(Track::from_value)
+                .filter(|track| track.track_type == track_type)
+                .map(TrackInfo::from)
+                .collect())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Switches to the next audio track and returns the newly selected track ID
+    pub async fn cycle_audio_track(&self, id: VideoId) -> Result<Option<i64>> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.cycle_audio_track()?;
+            client_guard.get_current_audio_track()
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Switches to the next subtitle track and returns the newly selected track ID
+    pub async fn cycle_subtitle_track(&self, id: VideoId) -> Result<Option<i64>> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.cycle_subtitle_track()?;
+            client_guard.get_current_subtitle_track()
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Seeks to a timecode such as `1:23:45`, `23:45.5` or `45`
+    pub async fn seek_timecode(&self, id: VideoId, timecode: &str) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.seek_timecode(timecode)?;
+            
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Skips to the next playlist entry
+    pub async fn playlist_next(&self, id: VideoId) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.playlist_next()?;
+            
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Goes back to the previous playlist entry
+    pub async fn playlist_prev(&self, id: VideoId) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.playlist_prev()?;
+            
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Advances the video by exactly one frame, pausing it if it is playing
+    pub async fn frame_step(&self, id: VideoId) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.frame_step()?;
+            
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Steps the video back by exactly one frame, pausing it if it is playing
+    pub async fn frame_back_step(&self, id: VideoId) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.frame_back_step()?;
+            
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Jumps to the playlist entry at `index` (zero-based)
+    pub async fn playlist_goto(&self, id: VideoId, index: usize) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.set_playlist_pos(index as i64)?;
+            
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Gets the entries of the instance's playlist
+    pub async fn get_playlist(&self, id: VideoId) -> Result<Vec<PlaylistEntry>> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            Ok(client_guard.get_playlist()?
+                .iter()
+                .filter_map(PlaylistEntry::from_value)
+                .collect())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Saves a screenshot of the current frame to `path`
+    pub async fn screenshot(&self, id: VideoId, path: &str, include_subtitles: bool) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.screenshot_to_file(path, include_subtitles)?;
+            
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Starts recording the stream to `path`, or stops recording when `None`
+    pub async fn set_stream_record(&self, id: VideoId, path: Option<PathBuf>) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.set_stream_record(path.as_deref())?;
+            
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Gets the chapters of the current media, empty if it has none
+    pub async fn get_chapters(&self, id: VideoId) -> Result<Vec<ChapterInfo>> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            match client_guard.get_chapters() {
+                Err(Error::MpvError(msg)) if msg.contains("property unavailable") => Ok(Vec::new()),
+                result => result,
+            }
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Gets the index of the current chapter, `-1` before the first chapter or when there are none
+    pub async fn current_chapter(&self, id: VideoId) -> Result<i64> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            match client_guard.get_chapter() {
+                Err(Error::MpvError(msg)) if msg.contains("property unavailable") => Ok(-1),
+                result => result,
+            }
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Jumps to the start of the chapter at `index` (zero-based)
+    pub async fn set_chapter(&self, id: VideoId, index: usize) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.set_chapter(index as i64)?;
+            
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Loops playback over a single chapter; the last chapter loops to the end of the file
+    pub async fn loop_chapter(&self, id: VideoId, chapter_index: usize) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            let chapters = client_guard.get_chapters()?;
+            let duration = client_guard.get_duration()?;
+            let (start, end) = chapter_loop_bounds(&chapters, chapter_index, duration)
+                .ok_or_else(|| Error::MpvError(format!("Invalid chapter index: {}", chapter_index)))?;
+            
+            client_guard.set_ab_loop(start, end)
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Stops looping a chapter set via `loop_chapter`
+    pub async fn clear_chapter_loop(&self, id: VideoId) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.clear_ab_loop()
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Resets speed, volume, mute, A-B loop, video filters, equalizer and delays to defaults
+    pub async fn reset_playback(&self, id: VideoId) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.reset_playback()?;
+            
+            // Keep the defaults across an mpv restart too
+            let mut state = instance.client_state.lock().unwrap();
+            state.speed = Some(1.0);
+            state.muted = Some(false);
+            state.volume = Some(100.0);
+            state.sub_delay = Some(0.0);
+            
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Gets the current zoom and pan of a video
+    pub async fn get_transform(&self, id: VideoId) -> Result<VideoTransform> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.get_transform()
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Sets the zoom and pan of a video, e.g. from pinch and drag gestures
+    pub async fn set_transform(&self, id: VideoId, transform: VideoTransform) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.set_transform(transform)
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Applies debanding settings live, e.g. from a "fix banding" slider
+    pub async fn set_deband(&self, id: VideoId, params: DebandParams) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.set_deband(params)
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Sets the volume for a video
+    pub async fn set_volume(&self, id: VideoId, volume: i32) -> Result<()> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            instance.client_state.lock().unwrap().volume = Some(volume as f64);
+            
+            if let Some(window) = self.command_debounce {
+                Self::queue_control(instance, window, |pending| pending.volume = Some(volume as f64));
+                return Ok(());
+            }
+            
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.set_volume(volume as f64)?;
+            
+            Ok(())
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Returns the IPC socket path of a running video, for attaching external tools or a second client.
+    /// Commands written by other clients may interleave with the manager's own traffic.
+    pub fn get_socket_path(&self, id: VideoId) -> Option<String> {
+        let instances = self.instances.lock().unwrap();
+        instances.get(&id).map(|instance| instance.socket_path.clone())
+    }
+    
+    /// Returns the settings that will be re-applied if mpv restarts
+    pub fn get_client_state(&self, id: VideoId) -> Result<ClientState> {
+        let instances = self.instances.lock().unwrap();
+        
+        match instances.get(&id) {
+            Some(instance) => Ok(instance.client_state.lock().unwrap().clone()),
+            None => Err(self.missing_instance(id)),
+        }
+    }
+    
+    /// Records mpv's current settings (including ones changed from the OSC) for re-application after a restart
+    pub async fn save_client_state(&self, id: VideoId) -> Result<ClientState> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            let state = ClientState::capture(&mut client_guard);
+            *instance.client_state.lock().unwrap() = state.clone();
+            
+            Ok(state)
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Dumps mpv's diagnostic state for a video, e.g. to attach to a bug report
+    pub async fn dump_state(&self, id: VideoId) -> Result<serde_json::Value> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            client_guard.dump_state()
+        } else {
+            Err(self.missing_instance(id))
+        }
+    }
+    
+    /// Lowers the volume to `to_fraction` of its current level, fading over `fade_ms`.
+    /// The previous volume is restored when the returned guard drops.
+    pub async fn duck(&self, id: VideoId, to_fraction: f64, fade_ms: u64) -> Result<DuckGuard> {
+        let client = {
+            let instances = self.instances.lock().unwrap();
+            match instances.get(&id) {
+                Some(instance) => instance.ipc_client.clone(),
+                None => return Err(self.missing_instance(id)),
+            }
+        };
+        
+        let previous_volume = client.lock().unwrap().get_volume()?;
+        let target = previous_volume * to_fraction.clamp(0.0, 1.0);
+        
+        // Step the volume down, the last step landing exactly on the target
+        let steps = (fade_ms / DUCK_FADE_STEP_MS).max(1);
+        for step in 1..=steps {
+            let volume = previous_volume + (target - previous_volume) * step as f64 / steps as f64;
+            client.lock().unwrap().set_volume(volume)?;
+            
+            if step < steps {
+                tokio::time::sleep(Duration::from_millis(DUCK_FADE_STEP_MS)).await;
+            }
+        }
+        
+        Ok(DuckGuard {
+            id,
+            client,
+            previous_volume,
+        })
+    }
+    
+    /// Gets detailed information about a video
+    pub async fn get_video_info(&self, id: VideoId) -> Result<VideoInfo> {
+        let instances = self.instances.lock().unwrap();
+        
+        if let Some(instance) = instances.get(&id) {
+            let client = instance.ipc_client.clone();
+            let mut client_guard = client.lock().unwrap();
+            
+            // Get position
+            let position = match client_guard.get_property("playback-time") {
+                Ok(pos) => pos.as_f64().unwrap_or(0.0),
+                Err(_) => 0.0,
+            };
+            
+            // Get duration
+            let duration = match client_guard.get_property("duration") {
+                Ok(dur) => dur.as_f64().unwrap_or(0.0),
+                Err(_) => 0.0,
             };
             
             // Get volume
@@ -1016,7 +2959,25 @@ impl VideoManager {
                 percent,
             })
         } else {
-            Err(Error::MpvError(format!("Video instance not found: {}", id.to_string())))
+            Err(self.missing_instance(id))
+        }
+    }
+}
+
+impl Drop for VideoManager {
+    fn drop(&mut self) {
+        if self.kill_on_drop {
+            return;
+        }
+        
+        // Release every instance before it drops so its process is left running
+        let mut instances = match self.instances.lock() {
+            Ok(instances) => instances,
+            Err(_) => return,
+        };
+        for (id, mut instance) in instances.drain() {
+            debug!("Leaving mpv running for video {} on manager drop", id.to_string());
+            let _ = Self::release_instance(&mut instance);
         }
     }
 }
@@ -1025,4 +2986,954 @@ impl Default for VideoManager {
     fn default() -> Self {
         Self::new()
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(unix)]
+    use crate::core::player::mock::MockMpv;
+    #[cfg(unix)]
+    use serde_json::json;
+    
+    /// Options that make the monitor and listener poll quickly
+    #[cfg(unix)]
+    fn test_options() -> PlaybackOptions {
+        PlaybackOptions {
+            poll_interval_ms: Some(MIN_POLL_INTERVAL_MS),
+            ..Default::default()
+        }
+    }
+    
+    /// Registers a mock mpv with the manager, using a long-running child as its process
+    #[cfg(unix)]
+    fn attach(manager: &VideoManager, mock: &MockMpv, options: PlaybackOptions) -> VideoId {
+        let process = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        VideoManager::attach_process(
+            VideoId::new(), process, mock.socket_path(), None, &options,
+            &manager.instances, &manager.event_subscribers,
+        ).unwrap()
+    }
+    
+    /// Whether the monitor of a video has given up on its connection
+    #[cfg(unix)]
+    fn monitor_stopped(manager: &VideoManager, id: VideoId) -> bool {
+        manager.instances.lock().unwrap()[&id].ipc_client.lock().unwrap().is_intentionally_closed()
+    }
+    
+    #[test]
+    fn window_options_inherit_manager_defaults() {
+        let defaults = WindowOptions {
+            borderless: Some(true),
+            always_on_top: Some(true),
+            size: Some((1280, 720)),
+            ..Default::default()
+        };
+        let manager = VideoManager::new().with_default_window(defaults);
+        
+        let window = manager.resolve_window(Some(WindowOptions {
+            position: Some((10, 20)),
+            ..Default::default()
+        })).unwrap();
+        
+        assert_eq!(window.borderless, Some(true));
+        assert_eq!(window.always_on_top, Some(true));
+        assert_eq!(window.size, Some((1280, 720)));
+        assert_eq!(window.position, Some((10, 20)));
+    }
+    
+    #[test]
+    fn window_options_per_call_override_wins() {
+        let defaults = WindowOptions {
+            borderless: Some(true),
+            always_on_top: Some(true),
+            opacity: Some(0.5),
+            ..Default::default()
+        };
+        let window = WindowOptions {
+            always_on_top: Some(false),
+            opacity: Some(1.0),
+            ..Default::default()
+        };
+        
+        let merged = window.merged_over(&defaults);
+        
+        assert_eq!(merged.always_on_top, Some(false));
+        assert_eq!(merged.opacity, Some(1.0));
+        assert_eq!(merged.borderless, Some(true));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn keep_open_from_config_reports_end_and_keeps_monitoring() {
+        let mock = MockMpv::start();
+        mock.set("keep-open", json!("yes"));
+        let manager = VideoManager::new();
+        let mut subscription = manager.subscribe().await;
+        let id = attach(&manager, &mock, test_options());
+        
+        mock.set("eof-reached", json!(true));
+        subscription.wait_for(|event| matches!(event, VideoEvent::Ended { id: event_id } if *event_id == id), Duration::from_secs(3))
+            .await.unwrap();
+        
+        // mpv stays on the last frame, so the instance is still monitored
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert!(!monitor_stopped(&manager, id));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn explicit_keep_open_no_ends_monitoring() {
+        let mock = MockMpv::start();
+        mock.set("keep-open", json!("yes"));
+        let manager = VideoManager::new();
+        let mut subscription = manager.subscribe().await;
+        let id = attach(&manager, &mock, PlaybackOptions {
+            keep_open: Some(KeepOpen::No),
+            ..test_options()
+        });
+        
+        mock.set("eof-reached", json!(true));
+        subscription.wait_for(|event| matches!(event, VideoEvent::Ended { id: event_id } if *event_id == id), Duration::from_secs(3))
+            .await.unwrap();
+        
+        assert!(crate::core::player::mock::wait_until(1000, || monitor_stopped(&manager, id)));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn user_data_is_kept_until_the_video_is_closed() {
+        let mock = MockMpv::start();
+        let manager = VideoManager::new();
+        let id = attach(&manager, &mock, PlaybackOptions {
+            user_data: Some(json!({"episode": 4, "show": "demo"})),
+            ..test_options()
+        });
+        
+        assert_eq!(manager.get_user_data(id).unwrap(), Some(json!({"episode": 4, "show": "demo"})));
+        
+        manager.close(id).await.unwrap();
+        assert!(manager.get_user_data(id).is_err());
+    }
+    
+    #[tokio::test]
+    async fn closed_from_another_thread_is_not_reported_twice() {
+        let manager = VideoManager::new();
+        let mut subscription = manager.subscribe().await;
+        let id = VideoId::new();
+        
+        // A control call on its own thread and the monitor both report the close
+        let hub = Arc::clone(&manager.event_subscribers);
+        std::thread::spawn(move || VideoManager::notify_subscribers(&hub, VideoEvent::Closed { id }))
+            .join().unwrap();
+        VideoManager::notify_subscribers(&manager.event_subscribers, VideoEvent::Closed { id });
+        VideoManager::notify_subscribers(&manager.event_subscribers, VideoEvent::Started { id });
+        
+        assert!(matches!(subscription.recv().await, Some(VideoEvent::Closed { .. })));
+        assert!(matches!(subscription.recv().await, Some(VideoEvent::Started { .. })));
+        
+        // Once the video is forgotten its state is cleaned up
+        manager.event_subscribers.forget(id);
+        assert!(manager.event_subscribers.notified_events.lock().unwrap().is_empty());
+    }
+    
+    #[tokio::test]
+    async fn lagging_subscriber_still_receives_terminal_events_once() {
+        let manager = VideoManager::new();
+        let mut subscription = manager.subscribe().await;
+        let id = VideoId::new();
+        let progress = |position: f64| VideoEvent::Progress { id, position, duration: 1000.0, percent: 0.0 };
+        
+        // The Closed is buried under more events than the channel can buffer
+        VideoManager::notify_subscribers(&manager.event_subscribers, progress(0.0));
+        VideoManager::notify_subscribers(&manager.event_subscribers, VideoEvent::Closed { id });
+        for position in 1..=(EVENT_CHANNEL_CAPACITY + 50) {
+            VideoManager::notify_subscribers(&manager.event_subscribers, progress(position as f64));
+        }
+        
+        let mut received = Vec::new();
+        while let Ok(Some(event)) = tokio::time::timeout(Duration::from_millis(100), subscription.recv()).await {
+            received.push(event);
+        }
+        
+        assert!(subscription.lagged() > 0);
+        let closed: Vec<usize> = received.iter().enumerate()
+            .filter(|(_, event)| matches!(event, VideoEvent::Closed { .. }))
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(closed, vec![0]);
+        assert!(matches!(received.last(), Some(VideoEvent::Progress { position, .. }) if *position == (EVENT_CHANNEL_CAPACITY + 50) as f64));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn pause_at_fires_once_playback_reaches_the_target() {
+        let mock = MockMpv::start();
+        mock.set("time-pos", json!(5.0));
+        mock.set("duration", json!(100.0));
+        let manager = VideoManager::new();
+        let mut subscription = manager.subscribe().await;
+        let id = attach(&manager, &mock, test_options());
+        
+        manager.pause_at(id, 10.0).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(mock.property("pause"), Some(json!(false)));
+        
+        mock.set("time-pos", json!(10.2));
+        subscription.wait_for(|event| matches!(event, VideoEvent::Paused { id: event_id } if *event_id == id), Duration::from_secs(3))
+            .await.unwrap();
+        assert_eq!(mock.property("pause"), Some(json!(true)));
+        assert!(manager.instances.lock().unwrap()[&id].pause_target.lock().unwrap().is_none());
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn end_file_error_is_reported_with_its_message() {
+        let mock = MockMpv::start();
+        let manager = VideoManager::new();
+        let mut subscription = manager.subscribe().await;
+        let id = attach(&manager, &mock, test_options());
+        
+        mock.emit(json!({"event": "end-file", "reason": "error", "file_error": "unrecognized file format"}));
+        
+        let event = subscription.wait_for(|event| matches!(event, VideoEvent::Error { .. }), Duration::from_secs(3))
+            .await.unwrap();
+        assert!(matches!(event, VideoEvent::Error { id: event_id, message } if event_id == id && message == "unrecognized file format"));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn thumbnails_are_written_and_position_restored() {
+        let mock = MockMpv::start();
+        mock.set("duration", json!(100.0));
+        mock.set("time-pos", json!(42.0));
+        mock.set("seeking", json!(false));
+        let manager = VideoManager::new();
+        let id = attach(&manager, &mock, test_options());
+        let dir = std::env::temp_dir().join(format!("playa-thumbnails-{}", uuid::Uuid::new_v4()));
+        
+        let paths = manager.generate_thumbnails(id, 4, dir.clone()).await.unwrap();
+        
+        assert_eq!(paths.len(), 4);
+        assert!(paths.iter().all(|path| path.exists()));
+        assert_eq!(mock.property("time-pos"), Some(json!(42.0)));
+        assert_eq!(mock.property("pause"), Some(json!(false)));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn thumbnails_are_capped_for_short_files() {
+        let mock = MockMpv::start();
+        mock.set("duration", json!(3.5));
+        mock.set("time-pos", json!(0.0));
+        let manager = VideoManager::new();
+        let id = attach(&manager, &mock, test_options());
+        let dir = std::env::temp_dir().join(format!("playa-thumbnails-{}", uuid::Uuid::new_v4()));
+        
+        let paths = manager.generate_thumbnails(id, 10, dir.clone()).await.unwrap();
+        
+        assert_eq!(paths.len(), 3);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    
+    #[tokio::test]
+    #[ignore = "requires mpv"]
+    async fn thumbnails_from_a_live_player() {
+        let manager = VideoManager::new();
+        let id = manager.play(
+            "av://lavfi:testsrc2=duration=10:size=320x180:rate=24".to_string(),
+            PlaybackOptions { start_time: Some(2.0), extra_args: vec!["--pause".to_string()], ..Default::default() },
+        ).await.unwrap();
+        let dir = std::env::temp_dir().join(format!("playa-thumbnails-{}", uuid::Uuid::new_v4()));
+        
+        let paths = manager.generate_thumbnails(id, 5, dir.clone()).await.unwrap();
+        let position = manager.instances.lock().unwrap()[&id].ipc_client.lock().unwrap().get_time_pos().unwrap();
+        manager.close(id).await.unwrap();
+        
+        assert_eq!(paths.len(), 5);
+        assert!(paths.iter().all(|path| path.exists()));
+        assert!((position - 2.0).abs() < 0.5);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn second_play_on_a_live_socket_is_rejected() {
+        let mock = MockMpv::start();
+        let manager = VideoManager::new();
+        let id = attach(&manager, &mock, test_options());
+        
+        let result = manager.play("video.mkv".to_string(), PlaybackOptions {
+            socket_path: Some(mock.socket_path()),
+            ..Default::default()
+        }).await;
+        
+        assert!(matches!(result, Err(Error::ConfigError(message)) if message.contains(&id.to_string())));
+        assert!(matches!(
+            manager.play_detached("video.mkv".to_string(), PlaybackOptions {
+                socket_path: Some(mock.socket_path()),
+                ..Default::default()
+            }).await,
+            Err(Error::ConfigError(_))
+        ));
+    }
+    
+    #[test]
+    fn socket_is_reserved_until_the_starting_video_releases_it() {
+        let manager = VideoManager::new();
+        
+        let first = manager.reserve_socket(Some("/tmp/playa-shared.sock")).unwrap();
+        assert!(first.is_some());
+        assert!(matches!(
+            manager.reserve_socket(Some("/tmp/playa-shared.sock")),
+            Err(Error::ConfigError(message)) if message.contains("a starting video")
+        ));
+        assert!(manager.reserve_socket(None).unwrap().is_none());
+        
+        drop(first);
+        assert!(manager.reserve_socket(Some("/tmp/playa-shared.sock")).unwrap().is_some());
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn play_async_returns_the_id_before_playback_is_up() {
+        let mock = MockMpv::start();
+        let manager = VideoManager::new();
+        // mpv is not reachable until the socket path points at the mock
+        let socket_path = std::env::temp_dir().join(format!("playa-test-{}.sock", uuid::Uuid::new_v4()));
+        
+        let (id, ready) = manager.play_async("video.mkv".to_string(), PlaybackOptions {
+            mpv_path: Some(mock.fake_binary()),
+            socket_path: Some(socket_path.display().to_string()),
+            socket_mode: Some(0o600),
+            ..test_options()
+        });
+        tokio::pin!(ready);
+        
+        assert!(tokio::time::timeout(Duration::from_millis(200), &mut ready).await.is_err());
+        assert!(!manager.instances.lock().unwrap().contains_key(&id));
+        
+        std::os::unix::fs::symlink(mock.socket_path(), &socket_path).unwrap();
+        let result = ready.await;
+        let _ = std::fs::remove_file(&socket_path);
+        
+        result.unwrap();
+        assert!(manager.instances.lock().unwrap().contains_key(&id));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn client_message_args_reach_subscribers() {
+        let mock = MockMpv::start();
+        let manager = VideoManager::new();
+        let mut subscription = manager.subscribe().await;
+        let id = attach(&manager, &mock, test_options());
+        
+        mock.emit(json!({"event": "client-message", "args": ["playa-button", "bookmark", "42.5"]}));
+        
+        let event = subscription.wait_for(|event| matches!(event, VideoEvent::ScriptMessage { .. }), Duration::from_secs(3))
+            .await.unwrap();
+        assert!(matches!(
+            event,
+            VideoEvent::ScriptMessage { id: event_id, args } if event_id == id && args == ["playa-button", "bookmark", "42.5"]
+        ));
+    }
+    
+    /// Subscriber that records the spans created and the ones entered
+    #[cfg(feature = "tracing")]
+    #[derive(Clone, Default)]
+    struct SpanRecorder {
+        spans: Arc<Mutex<Vec<&'static tracing::Metadata<'static>>>>,
+        entered: Arc<Mutex<Vec<&'static tracing::Metadata<'static>>>>,
+    }
+    
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for SpanRecorder {
+        fn enabled(&self, _metadata: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+        
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(span.metadata());
+            tracing::span::Id::from_u64(spans.len() as u64)
+        }
+        
+        fn record(&self, _span: &tracing::span::Id, _values: &tracing::span::Record<'_>) {}
+        
+        fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+        
+        fn event(&self, _event: &tracing::Event<'_>) {}
+        
+        fn enter(&self, span: &tracing::span::Id) {
+            let metadata = self.spans.lock().unwrap()[span.into_u64() as usize - 1];
+            self.entered.lock().unwrap().push(metadata);
+        }
+        
+        fn exit(&self, _span: &tracing::span::Id) {}
+    }
+    
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn play_is_instrumented_with_the_video_id() {
+        let recorder = SpanRecorder::default();
+        let _guard = tracing::subscriber::set_default(recorder.clone());
+        let manager = VideoManager::new();
+        
+        let _ = manager.play("video.mkv".to_string(), PlaybackOptions {
+            mpv_path: Some(PathBuf::from("/nonexistent/mpv")),
+            ..Default::default()
+        }).await;
+        
+        let entered = recorder.entered.lock().unwrap();
+        let play = entered.iter().find(|span| span.name() == "play").expect("play span was not entered");
+        assert!(play.fields().field("video_id").is_some());
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn client_state_is_reapplied_after_mpv_restarts() {
+        let mock = MockMpv::start();
+        let manager = VideoManager::new();
+        let id = attach(&manager, &mock, test_options());
+        manager.set_volume(id, 40).await.unwrap();
+        manager.set_speed(id, 1.5).await.unwrap();
+        
+        // A restarted mpv comes back with a new pid and its default settings
+        mock.set("pid", json!(2000));
+        mock.set("volume", json!(100.0));
+        mock.set("speed", json!(1.0));
+        mock.disconnect_all();
+        
+        assert!(crate::core::player::mock::wait_until(3000, || {
+            mock.property("volume") == Some(json!(40.0)) && mock.property("speed") == Some(json!(1.5))
+        }));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn socket_path_is_returned_for_a_live_instance() {
+        let mock = MockMpv::start();
+        let manager = VideoManager::new();
+        let id = attach(&manager, &mock, test_options());
+        
+        let socket_path = manager.get_socket_path(id).unwrap();
+        assert_eq!(socket_path, mock.socket_path());
+        assert!(manager.get_socket_path(VideoId::new()).is_none());
+        
+        // A second client can attach to the same socket alongside the manager's own
+        let mut external = MpvIpcClient::connect(&socket_path).unwrap();
+        assert_eq!(external.get_property("pid").unwrap(), json!(1000));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn burst_of_seeks_becomes_one_final_seek() {
+        let mock = MockMpv::start();
+        let manager = VideoManager::new().with_command_debounce(Duration::from_millis(100));
+        let id = attach(&manager, &mock, test_options());
+        
+        for position in [10.0, 20.0, 30.0, 40.0, 50.0] {
+            manager.seek(id, position).await.unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        
+        let seeks: Vec<_> = mock.actions().into_iter()
+            .filter(|command| command.get(0).and_then(|name| name.as_str()) == Some("seek"))
+            .collect();
+        assert_eq!(seeks.len(), 1);
+        assert_eq!(seeks[0][1], json!(50.0));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn process_survives_manager_drop_without_kill_on_drop() {
+        let mock = MockMpv::start();
+        let manager = VideoManager::new().with_kill_on_drop(false);
+        let id = attach(&manager, &mock, test_options());
+        let pid = manager.instances.lock().unwrap()[&id].process.as_ref().unwrap().id().to_string();
+        
+        drop(manager);
+        
+        let alive = std::process::Command::new("kill").args(["-0", &pid]).status().unwrap().success();
+        let _ = std::process::Command::new("kill").arg(&pid).status();
+        assert!(alive);
+        assert!(!mock.actions().iter().any(|command| command.get(0) == Some(&json!("quit"))));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn seek_completion_reports_the_landed_position() {
+        let mock = MockMpv::start();
+        let manager = VideoManager::new();
+        let mut subscription = manager.subscribe().await;
+        let id = attach(&manager, &mock, test_options());
+        
+        // A restart without a preceding seek, such as after loading, is not a seek completion
+        mock.emit(json!({"event": "playback-restart"}));
+        mock.set("time-pos", json!(12.5));
+        mock.emit(json!({"event": "seek"}));
+        mock.emit(json!({"event": "playback-restart"}));
+        
+        let event = subscription.wait_for(|event| matches!(event, VideoEvent::SeekCompleted { .. }), Duration::from_secs(3))
+            .await.unwrap();
+        assert!(matches!(event, VideoEvent::SeekCompleted { id: event_id, position } if event_id == id && position == 12.5));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn resolution_changes_are_reported_once_each() {
+        let mock = MockMpv::start();
+        let manager = VideoManager::new();
+        let mut subscription = manager.subscribe().await;
+        let id = attach(&manager, &mock, test_options());
+        
+        for (width, height) in [(1280, 720), (1280, 720), (1920, 1080)] {
+            mock.emit(json!({"event": "property-change", "name": "video-params", "data": {"w": width, "h": height, "pixelformat": "yuv420p"}}));
+        }
+        
+        let is_params = |event: &VideoEvent| matches!(event, VideoEvent::VideoParamsChanged { .. });
+        let first = subscription.wait_for(is_params, Duration::from_secs(3)).await.unwrap();
+        let second = subscription.wait_for(is_params, Duration::from_secs(3)).await.unwrap();
+        
+        assert!(matches!(first, VideoEvent::VideoParamsChanged { id: event_id, width: 1280, height: 720 } if event_id == id));
+        assert!(matches!(second, VideoEvent::VideoParamsChanged { width: 1920, height: 1080, .. }));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn loop_chapter_sets_ab_loop_from_the_chapter_list() {
+        let mock = MockMpv::start();
+        mock.set("chapter-list", json!([{"title": "A", "time": 0.0}, {"title": "B", "time": 42.0}]));
+        mock.set("duration", json!(90.0));
+        let manager = VideoManager::new();
+        let id = attach(&manager, &mock, test_options());
+        
+        manager.loop_chapter(id, 1).await.unwrap();
+        assert_eq!((mock.property("ab-loop-a"), mock.property("ab-loop-b")), (Some(json!(42.0)), Some(json!(90.0))));
+        
+        assert!(manager.loop_chapter(id, 2).await.is_err());
+        manager.clear_chapter_loop(id).await.unwrap();
+        assert_eq!(mock.property("ab-loop-a"), Some(json!("no")));
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn shutdown_closes_every_video_on_the_given_runtime() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let mock = MockMpv::start();
+        let manager = VideoManager::new();
+        let mut subscription = runtime.block_on(manager.subscribe());
+        let id = attach(&manager, &mock, test_options());
+        let pid = manager.instances.lock().unwrap()[&id].process.as_ref().unwrap().id().to_string();
+        
+        manager.shutdown(&runtime).unwrap();
+        
+        assert!(manager.instances.lock().unwrap().is_empty());
+        runtime.block_on(subscription.wait_for(
+            |event| matches!(event, VideoEvent::Closed { id: event_id } if *event_id == id),
+            Duration::from_secs(1),
+        )).unwrap();
+        // The process was killed and reaped rather than left as a zombie
+        assert!(!std::process::Command::new("kill").args(["-0", &pid]).status().unwrap().success());
+    }
+    
+    #[test]
+    fn shutdown_is_refused_inside_a_runtime() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let manager = VideoManager::new();
+        
+        let result = runtime.block_on(async { manager.shutdown(&runtime) });
+        assert!(matches!(result, Err(Error::MpvError(message)) if message.contains("close_all")));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn cycling_tracks_sends_cycle_and_reads_the_new_track() {
+        let mock = MockMpv::start();
+        // The mock does not switch tracks itself, so these stand for where each cycle lands
+        mock.set("aid", json!(2));
+        mock.set("sid", json!("no"));
+        let manager = VideoManager::new();
+        let id = attach(&manager, &mock, test_options());
+        
+        assert_eq!(manager.cycle_audio_track(id).await.unwrap(), Some(2));
+        assert_eq!(manager.cycle_subtitle_track(id).await.unwrap(), None);
+        
+        let cycles: Vec<_> = mock.actions().into_iter()
+            .filter(|command| command.get(0) == Some(&json!("cycle")))
+            .collect();
+        assert_eq!(cycles, [json!(["cycle", "aid"]), json!(["cycle", "sid"])]);
+    }
+    
+    #[test]
+    fn last_playlist_entry_is_matched_by_id_or_flag() {
+        let playlist = [json!({"filename": "a.mkv", "id": 1}), json!({"filename": "b.mkv", "id": 2})];
+        assert!(!is_last_playlist_entry(&playlist, Some(1)));
+        assert!(is_last_playlist_entry(&playlist, Some(2)));
+        
+        let without_ids = [json!({"filename": "a.mkv"}), json!({"filename": "b.mkv", "playing": true})];
+        assert!(is_last_playlist_entry(&without_ids, None));
+        assert!(is_last_playlist_entry(&[], None));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn playlist_ended_fires_only_after_the_last_item() {
+        let mock = MockMpv::start();
+        mock.set("playlist", json!([{"filename": "a.mkv", "id": 1}, {"filename": "b.mkv", "id": 2}]));
+        let manager = VideoManager::new();
+        let mut subscription = manager.subscribe().await;
+        let id = attach(&manager, &mock, test_options());
+        let is_playlist_end = |event: &VideoEvent| matches!(event, VideoEvent::PlaylistEnded { .. });
+        
+        mock.emit(json!({"event": "end-file", "reason": "eof", "playlist_entry_id": 1}));
+        assert!(subscription.wait_for(is_playlist_end, Duration::from_millis(500)).await.is_err());
+        
+        mock.emit(json!({"event": "end-file", "reason": "eof", "playlist_entry_id": 2}));
+        let event = subscription.wait_for(is_playlist_end, Duration::from_secs(3)).await.unwrap();
+        assert!(matches!(event, VideoEvent::PlaylistEnded { id: event_id } if event_id == id));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn duck_lowers_volume_and_restores_it_on_drop() {
+        let mock = MockMpv::start();
+        mock.set("volume", json!(80.0));
+        let manager = VideoManager::new();
+        let id = attach(&manager, &mock, test_options());
+        
+        let guard = manager.duck(id, 0.25, 100).await.unwrap();
+        assert_eq!(guard.previous_volume(), 80.0);
+        assert_eq!(mock.property("volume"), Some(json!(20.0)));
+        
+        // The fade steps down rather than jumping straight to the target
+        let volume_writes = mock.actions().into_iter()
+            .filter(|command| command.get(0) == Some(&json!("set_property")) && command.get(1) == Some(&json!("volume")))
+            .count();
+        assert_eq!(volume_writes, 5);
+        
+        drop(guard);
+        assert_eq!(mock.property("volume"), Some(json!(80.0)));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn wait_for_exit_resolves_with_the_exit_code() {
+        let mock = MockMpv::start();
+        let manager = VideoManager::new();
+        let process = std::process::Command::new("sh").args(["-c", "sleep 0.3; exit 3"]).spawn().unwrap();
+        let id = VideoManager::attach_process(
+            VideoId::new(), process, mock.socket_path(), None, &test_options(),
+            &manager.instances, &manager.event_subscribers,
+        ).unwrap();
+        
+        let code = tokio::time::timeout(Duration::from_secs(3), manager.wait_for_exit(id)).await.unwrap();
+        assert_eq!(code, Some(3));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn wait_for_exit_resolves_with_none_when_closed_first() {
+        let mock = MockMpv::start();
+        let manager = VideoManager::new();
+        let id = attach(&manager, &mock, test_options());
+        
+        let exit = tokio::spawn(manager.wait_for_exit(id));
+        manager.close(id).await.unwrap();
+        
+        assert_eq!(tokio::time::timeout(Duration::from_secs(3), exit).await.unwrap().unwrap(), None);
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn progress_only_monitoring_emits_no_pause_events() {
+        let mock = MockMpv::start();
+        mock.set("duration", json!(100.0));
+        mock.set("time-pos", json!(1.0));
+        let manager = VideoManager::new();
+        let mut subscription = manager.subscribe().await;
+        let id = attach(&manager, &mock, PlaybackOptions {
+            observe: ObserveSet::progress_only(),
+            ..test_options()
+        });
+        
+        let at = |target: f64| move |event: &VideoEvent| matches!(event, VideoEvent::Progress { position, .. } if *position == target);
+        subscription.wait_for(at(1.0), Duration::from_secs(3)).await.unwrap();
+        
+        mock.set("pause", json!(true));
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        mock.set("time-pos", json!(2.0));
+        
+        let mut events = Vec::new();
+        loop {
+            let event = tokio::time::timeout(Duration::from_secs(3), subscription.recv()).await.unwrap().unwrap();
+            let done = at(2.0)(&event);
+            events.push(event);
+            if done {
+                break;
+            }
+        }
+        assert!(!events.iter().any(|event| matches!(event, VideoEvent::Paused { .. } | VideoEvent::Resumed { .. })));
+        assert!(manager.instances.lock().unwrap().contains_key(&id));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn reset_playback_issues_every_reset() {
+        let mock = MockMpv::start();
+        for (property, value) in [("speed", json!(2.0)), ("mute", json!(true)), ("volume", json!(30)), ("sub-delay", json!(1.5))] {
+            mock.set(property, value);
+        }
+        let manager = VideoManager::new();
+        let id = attach(&manager, &mock, test_options());
+        
+        manager.reset_playback(id).await.unwrap();
+        
+        for (property, value) in [
+            ("speed", json!(1.0)),
+            ("mute", json!(false)),
+            ("volume", json!(100)),
+            ("ab-loop-a", json!("no")),
+            ("ab-loop-b", json!("no")),
+            ("brightness", json!(0)),
+            ("contrast", json!(0)),
+            ("saturation", json!(0)),
+            ("gamma", json!(0)),
+            ("hue", json!(0)),
+            ("audio-delay", json!(0.0)),
+            ("sub-delay", json!(0.0)),
+        ] {
+            assert_eq!(mock.property(property), Some(value), "{} was not reset", property);
+        }
+        assert!(mock.actions().contains(&json!(["vf", "clr", ""])));
+        
+        let state = manager.get_client_state(id).unwrap();
+        assert_eq!((state.speed, state.muted), (Some(1.0), Some(false)));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn metadata_changes_reach_subscribers() {
+        let mock = MockMpv::start();
+        let manager = VideoManager::new();
+        let mut subscription = manager.subscribe().await;
+        let id = attach(&manager, &mock, test_options());
+        
+        mock.emit(json!({"event": "property-change", "name": "metadata", "data": {"artist": "Radio One", "track": 7}}));
+        
+        let is_metadata = |event: &VideoEvent| matches!(event, VideoEvent::MetadataChanged { .. });
+        let event = subscription.wait_for(is_metadata, Duration::from_secs(3)).await.unwrap();
+        let VideoEvent::MetadataChanged { id: event_id, metadata } = event else { unreachable!() };
+        assert_eq!(event_id, id);
+        assert_eq!(metadata.get("artist").map(String::as_str), Some("Radio One"));
+        assert_eq!(metadata.get("track").map(String::as_str), Some("7"));
+        
+        mock.emit(json!({"event": "property-change", "name": "metadata/by-key/icy-title", "data": "Band - Song"}));
+        
+        let event = subscription.wait_for(is_metadata, Duration::from_secs(3)).await.unwrap();
+        let VideoEvent::MetadataChanged { metadata, .. } = event else { unreachable!() };
+        assert_eq!(metadata.get("icy-title").map(String::as_str), Some("Band - Song"));
+        assert_eq!(metadata.get("artist").map(String::as_str), Some("Radio One"));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn prebuffered_window_is_shown_once_the_cache_crosses_the_threshold() {
+        let mock = MockMpv::start();
+        mock.set("window-minimized", json!(true));
+        mock.set("pause", json!(true));
+        mock.set("demuxer-cache-time", json!(1.0));
+        let manager = VideoManager::new();
+        let mut subscription = manager.subscribe().await;
+        attach(&manager, &mock, PlaybackOptions {
+            prebuffer_secs: Some(3.0),
+            ..test_options()
+        });
+        
+        let is_started = |event: &VideoEvent| matches!(event, VideoEvent::Started { .. });
+        assert!(subscription.wait_for(is_started, Duration::from_millis(500)).await.is_err());
+        assert_eq!(mock.property("window-minimized"), Some(json!(true)));
+        assert_eq!(mock.property("pause"), Some(json!(true)));
+        
+        mock.set("demuxer-cache-time", json!(3.5));
+        
+        subscription.wait_for(is_started, Duration::from_secs(3)).await.unwrap();
+        assert_eq!(mock.property("window-minimized"), Some(json!(false)));
+        assert_eq!(mock.property("pause"), Some(json!(false)));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn play_detached_returns_the_id_before_mpv_is_spawned() {
+        let mock = MockMpv::start();
+        let manager = VideoManager::new();
+        // The spawn waits for the socket, which only appears once it points at the mock
+        let socket_path = std::env::temp_dir().join(format!("playa-test-{}.sock", uuid::Uuid::new_v4()));
+        
+        let detached = manager.play_detached("video.mkv".to_string(), PlaybackOptions {
+            mpv_path: Some(mock.fake_binary()),
+            socket_path: Some(socket_path.display().to_string()),
+            socket_mode: Some(0o600),
+            ..test_options()
+        });
+        let id = tokio::time::timeout(Duration::from_millis(200), detached).await
+            .expect("play_detached blocked on the spawn")
+            .unwrap();
+        
+        assert!(matches!(manager.pause(id).await, Err(Error::MpvError(message)) if message == "IPC not yet ready"));
+        // The socket stays reserved while mpv is still being spawned
+        assert!(matches!(
+            manager.play_detached("video.mkv".to_string(), PlaybackOptions {
+                socket_path: Some(socket_path.display().to_string()),
+                ..Default::default()
+            }).await,
+            Err(Error::ConfigError(_))
+        ));
+        
+        std::os::unix::fs::symlink(mock.socket_path(), &socket_path).unwrap();
+        let attached = crate::core::player::mock::wait_until(3000, || manager.instances.lock().unwrap().contains_key(&id));
+        let _ = std::fs::remove_file(&socket_path);
+        
+        assert!(attached);
+        manager.pause(id).await.unwrap();
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn play_and_wait_reports_a_video_that_ends_early() {
+        let mock = MockMpv::start();
+        mock.set("time-pos", json!(42.0));
+        mock.set("duration", json!(600.0));
+        mock.set("eof-reached", json!(true));
+        let manager = VideoManager::new();
+        
+        let started = std::time::Instant::now();
+        let outcome = manager.play_and_wait("video.mkv".to_string(), PlaybackOptions {
+            mpv_path: Some(mock.fake_binary()),
+            socket_path: Some(mock.socket_path()),
+            ..test_options()
+        }, Duration::from_secs(10)).await.unwrap();
+        
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(outcome.completed);
+        assert_eq!(outcome.watched_seconds, 42.0);
+        assert!(manager.instances.lock().unwrap().is_empty());
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn eof_does_not_end_a_looping_video() {
+        let mock = MockMpv::start();
+        let manager = VideoManager::new();
+        let mut subscription = manager.subscribe().await;
+        let id = attach(&manager, &mock, test_options());
+        
+        manager.set_loop(id, LoopMode::File(0)).await.unwrap();
+        assert_eq!(mock.property("loop-file"), Some(json!("inf")));
+        mock.set("eof-reached", json!(true));
+        
+        let is_ended = |event: &VideoEvent| matches!(event, VideoEvent::Ended { .. });
+        assert!(subscription.wait_for(is_ended, Duration::from_millis(600)).await.is_err());
+        assert!(!monitor_stopped(&manager, id));
+        
+        manager.set_loop(id, LoopMode::Off).await.unwrap();
+        subscription.wait_for(is_ended, Duration::from_secs(3)).await.unwrap();
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn instances_are_filtered_by_tag() {
+        let first_mock = MockMpv::start();
+        let second_mock = MockMpv::start();
+        let third_mock = MockMpv::start();
+        let manager = VideoManager::new();
+        let tagged = |tag: &str| PlaybackOptions {
+            instance_tag: Some(tag.to_string()),
+            ..test_options()
+        };
+        
+        let first = attach(&manager, &first_mock, tagged("player"));
+        let second = attach(&manager, &second_mock, tagged("preview"));
+        let third = attach(&manager, &third_mock, tagged("player"));
+        
+        let mut players = manager.get_instances_by_tag("player");
+        players.sort_by_key(|id| id.to_string());
+        let mut expected = vec![first, third];
+        expected.sort_by_key(|id| id.to_string());
+        assert_eq!(players, expected);
+        assert_eq!(manager.get_instances_by_tag("preview"), vec![second]);
+        assert!(manager.get_instances_by_tag("other").is_empty());
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn shared_shutdown_closes_videos_on_the_shared_runtime() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let mock = MockMpv::start();
+        let manager = VideoManager::new();
+        let id = attach(&manager, &mock, test_options());
+        let shared = tokio::sync::Mutex::new(manager);
+        
+        VideoManager::shutdown_shared(&shared, &runtime).unwrap();
+        
+        let manager = shared.try_lock().unwrap();
+        assert!(manager.instances.lock().unwrap().is_empty());
+        assert!(matches!(manager.missing_instance(id), Error::MpvError(message) if message.contains("not found")));
+    }
+    
+    #[test]
+    fn shared_shutdown_is_refused_inside_a_runtime() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let shared = tokio::sync::Mutex::new(VideoManager::new());
+        
+        let result = runtime.block_on(async { VideoManager::shutdown_shared(&shared, &runtime) });
+        assert!(matches!(result, Err(Error::MpvError(message)) if message.contains("close_all")));
+    }
+    
+    #[test]
+    fn shared_shutdown_gives_up_on_a_busy_manager() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let shared = tokio::sync::Mutex::new(VideoManager::new());
+        let _busy = shared.try_lock().unwrap();
+        
+        let started = std::time::Instant::now();
+        let result = VideoManager::shutdown_shared(&shared, &runtime);
+        
+        assert!(matches!(result, Err(Error::MpvError(message)) if message.contains("Timed out")));
+        assert!(started.elapsed() < Duration::from_millis(SHUTDOWN_LOCK_TIMEOUT_MS * 3));
+    }
+    
+    #[tokio::test]
+    async fn play_blocking_is_refused_inside_a_runtime() {
+        let manager = VideoManager::new();
+        
+        let result = manager.play_blocking("video.mkv".to_string(), test_options());
+        assert!(matches!(result, Err(Error::MpvError(message)) if message.contains("use play instead")));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn update_window_attempts_every_property_when_one_fails() {
+        let mock = MockMpv::start();
+        mock.fail_property("alpha", "property not found");
+        let manager = VideoManager::new();
+        let id = attach(&manager, &mock, test_options());
+        
+        let result = manager.update_window(id, WindowOptions {
+            position: Some((10, 20)),
+            size: Some((1280, 720)),
+            always_on_top: Some(true),
+            opacity: Some(0.5),
+            start_hidden: Some(true),
+            ..Default::default()
+        }).await;
+        
+        assert!(matches!(result, Err(Error::MpvError(message)) if message.contains("alpha") && !message.contains("ontop")));
+        assert_eq!(mock.property("window-pos"), Some(json!("10+20")));
+        assert_eq!(mock.property("geometry"), Some(json!("1280x720")));
+        assert_eq!(mock.property("ontop"), Some(json!(true)));
+        assert_eq!(mock.property("window-minimized"), Some(json!(true)));
+        assert_eq!(mock.property("alpha"), None);
+    }
+}
@@ -0,0 +1,331 @@
+use std::collections::HashSet;
+use std::process::Child;
+use std::sync::{Arc, Mutex};
+
+use log::{debug, error, warn};
+use tokio::task::JoinHandle as TokioJoinHandle;
+
+use crate::core::player::ipc::{LoadMode, MpvIpcClient};
+use crate::core::player::process::{SpawnOptions, spawn_mpv_idle};
+use crate::Result;
+
+use super::{PlaybackOptions, VideoEvent, VideoId, VideoManager};
+
+/// A pre-spawned mpv process waiting for media
+struct IdleInstance {
+    process: Child,
+    socket_path: String,
+}
+
+/// A pool of pre-spawned idle mpv instances for near-instant playback start.
+///
+/// Each `play` takes an idle instance and loads the media over IPC instead of
+/// spawning a new process. When a pooled video ends, its process is returned to
+/// the pool. All pooled processes share the spawn options given at creation, so
+/// per-call `preset` and `extra_args` are not applied; start time, title and
+/// window options are applied at load time.
+pub struct VideoManagerPool {
+    manager: Arc<VideoManager>,
+    idle: Arc<Mutex<Vec<IdleInstance>>>,
+    pooled: Arc<Mutex<HashSet<VideoId>>>,
+    size: usize,
+    spawn_options: SpawnOptions,
+    recycle_task: Option<TokioJoinHandle<()>>,
+}
+
+impl VideoManagerPool {
+    /// Creates a pool and pre-spawns `size` idle mpv instances
+    pub async fn new(size: usize, spawn_options: SpawnOptions) -> Result<Self> {
+        let mut instances = Vec::with_capacity(size);
+        for _ in 0..size {
+            match Self::spawn_idle(spawn_options.clone()).await {
+                Ok(instance) => instances.push(instance),
+                Err(e) => {
+                    for instance in instances {
+                        discard(instance.process);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        
+        Ok(Self::with_idle(instances, size, spawn_options).await)
+    }
+    
+    /// Creates a pool around already running idle instances
+    async fn with_idle(instances: Vec<IdleInstance>, size: usize, spawn_options: SpawnOptions) -> Self {
+        let manager = Arc::new(VideoManager::new());
+        let idle = Arc::new(Mutex::new(instances));
+        let pooled = Arc::new(Mutex::new(HashSet::new()));
+        
+        let recycle_task = Self::start_recycling(
+            Arc::clone(&manager),
+            Arc::clone(&idle),
+            Arc::clone(&pooled),
+            size,
+        ).await;
+        
+        Self {
+            manager,
+            idle,
+            pooled,
+            size,
+            spawn_options,
+            recycle_task: Some(recycle_task),
+        }
+    }
+    
+    /// Returns the manager that owns the videos started from this pool
+    pub fn manager(&self) -> &VideoManager {
+        &self.manager
+    }
+    
+    /// Returns the number of idle instances currently available
+    pub fn idle_count(&self) -> usize {
+        self.idle.lock().unwrap().len()
+    }
+    
+    /// Plays a video, reusing an idle instance when one is available
+    pub async fn play(&self, source: String, options: PlaybackOptions) -> Result<VideoId> {
+        let idle_instance = self.idle.lock().unwrap().pop();
+        
+        let Some(IdleInstance { process, socket_path }) = idle_instance else {
+            debug!("No idle mpv instance available, spawning a new one");
+            return self.manager.play(source, options).await;
+        };
+        
+        debug!("Reusing idle mpv instance at {}", socket_path);
+        let load_options = options.clone();
+        let load_socket_path = socket_path.clone();
+        let loaded = tokio::task::spawn_blocking(move || {
+            Self::load_into(&load_socket_path, &source, &load_options)
+        }).await.unwrap();
+        
+        if let Err(e) = loaded {
+            error!("Failed to load media into idle mpv instance: {}", e);
+            discard(process);
+            return Err(e);
+        }
+        
+        let window = options.window.clone();
+        let id = self.manager.adopt(process, socket_path, options).await?;
+        self.pooled.lock().unwrap().insert(id);
+        
+        if let Some(window) = window {
+            if let Err(e) = self.manager.update_window(id, window).await {
+                warn!("Failed to apply window options to pooled video {}: {}", id.to_string(), e);
+            }
+        }
+        
+        self.refill();
+        
+        Ok(id)
+    }
+    
+    /// Loads media into an idle mpv instance over IPC
+    fn load_into(socket_path: &str, source: &str, options: &PlaybackOptions) -> Result<()> {
+        let mut client = MpvIpcClient::connect(socket_path)?;
+        
+        // Passed as per-file options, so a recycled instance does not carry them to its next video
+        let mut file_options = Vec::new();
+        if let Some(start_time) = options.start_time {
+            file_options.push(("start", start_time.to_string()));
+        }
+        if let Some(title) = &options.title {
+            file_options.push(("title", title.clone()));
+        }
+        
+        client.load_file_with_options(source, LoadMode::Replace, &file_options)?;
+        client.close();
+        
+        Ok(())
+    }
+    
+    /// Spawns a replacement idle instance in the background
+    fn refill(&self) {
+        let idle = Arc::clone(&self.idle);
+        let spawn_options = self.spawn_options.clone();
+        let size = self.size;
+        
+        tokio::spawn(async move {
+            match Self::spawn_idle(spawn_options).await {
+                Ok(instance) => {
+                    let mut idle = idle.lock().unwrap();
+                    if idle.len() < size {
+                        idle.push(instance);
+                    } else {
+                        discard(instance.process);
+                    }
+                }
+                Err(e) => error!("Failed to refill mpv pool: {}", e),
+            }
+        });
+    }
+    
+    /// Spawns a single idle mpv instance
+    async fn spawn_idle(spawn_options: SpawnOptions) -> Result<IdleInstance> {
+        tokio::task::spawn_blocking(move || {
+            let (process, socket_path) = spawn_mpv_idle(&spawn_options)?;
+            Ok(IdleInstance { process, socket_path })
+        }).await.unwrap()
+    }
+    
+    /// Returns ended pooled instances to the idle list
+    async fn start_recycling(
+        manager: Arc<VideoManager>,
+        idle: Arc<Mutex<Vec<IdleInstance>>>,
+        pooled: Arc<Mutex<HashSet<VideoId>>>,
+        size: usize,
+    ) -> TokioJoinHandle<()> {
+        let mut subscription = manager.subscribe().await;
+        
+        tokio::spawn(async move {
+            while let Some(event) = subscription.recv().await {
+                match event {
+                    // With `--idle=yes`, mpv returns to idle at the end of a file, which the
+                    // monitor may report as either Ended or Closed; a video closed through the
+                    // manager is already gone, so `detach` returns nothing for it
+                    VideoEvent::Ended { id } | VideoEvent::Closed { id } => {
+                        if !pooled.lock().unwrap().remove(&id) {
+                            continue;
+                        }
+                        
+                        let Some((process, socket_path)) = manager.detach(id).await else {
+                            continue;
+                        };
+                        
+                        if idle.lock().unwrap().len() >= size {
+                            discard(process);
+                            continue;
+                        }
+                        
+                        // Make sure mpv is back to idle before reusing it
+                        let stop_socket_path = socket_path.clone();
+                        let stopped = tokio::task::spawn_blocking(move || -> Result<()> {
+                            let mut client = MpvIpcClient::connect(&stop_socket_path)?;
+                            client.command("stop", &[])?;
+                            client.close();
+                            Ok(())
+                        }).await.unwrap();
+                        
+                        match stopped {
+                            Ok(()) => {
+                                debug!("Recycled mpv instance at {} into the pool", socket_path);
+                                idle.lock().unwrap().push(IdleInstance { process, socket_path });
+                            }
+                            Err(e) => {
+                                warn!("Failed to recycle mpv instance, discarding it: {}", e);
+                                discard(process);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        })
+    }
+}
+
+impl Drop for VideoManagerPool {
+    fn drop(&mut self) {
+        if let Some(task) = self.recycle_task.take() {
+            task.abort();
+        }
+        
+        // Idle processes are owned by the pool, so terminate them with it
+        if let Ok(mut idle) = self.idle.lock() {
+            for instance in idle.drain(..) {
+                discard(instance.process);
+            }
+        }
+    }
+}
+
+/// Terminates a pooled mpv process and reaps it, so it does not linger as a zombie
+fn discard(mut process: Child) {
+    let _ = process.kill();
+    let _ = process.wait();
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::core::player::mock::MockMpv;
+    use serde_json::{json, Value};
+    use std::process::Command;
+    
+    /// An idle instance backed by a mock mpv and a long-running child process
+    fn idle_instance(mock: &MockMpv) -> IdleInstance {
+        IdleInstance {
+            process: Command::new("sleep").arg("30").spawn().unwrap(),
+            socket_path: mock.socket_path(),
+        }
+    }
+    
+    /// Spawn options that make background refills fail right away
+    fn no_refill() -> SpawnOptions {
+        SpawnOptions {
+            mpv_path: Some("/nonexistent/mpv".into()),
+            ..Default::default()
+        }
+    }
+    
+    /// Options that make the adopted videos poll quickly, so they shut down fast
+    fn fast_options() -> PlaybackOptions {
+        PlaybackOptions {
+            poll_interval_ms: Some(100),
+            ..Default::default()
+        }
+    }
+    
+    fn loadfile_commands(mock: &MockMpv) -> Vec<Value> {
+        mock.commands().into_iter()
+            .filter(|command| command.get("name") == Some(&json!("loadfile")) || command.get(0) == Some(&json!("loadfile")))
+            .collect()
+    }
+    
+    #[tokio::test]
+    async fn play_reuses_an_idle_process() {
+        let mock = MockMpv::start();
+        let instance = idle_instance(&mock);
+        let pid = instance.process.id();
+        let pool = VideoManagerPool::with_idle(vec![instance], 1, no_refill()).await;
+        
+        let id = pool.play("video.mkv".to_string(), PlaybackOptions {
+            start_time: Some(30.0),
+            title: Some("Demo, part 1".to_string()),
+            ..fast_options()
+        }).await.unwrap();
+        
+        assert_eq!(pool.idle_count(), 0);
+        let instances = pool.manager.instances.lock().unwrap();
+        assert_eq!(instances[&id].process.as_ref().map(Child::id), Some(pid));
+        drop(instances);
+        
+        assert_eq!(loadfile_commands(&mock), vec![json!({
+            "name": "loadfile",
+            "url": "video.mkv",
+            "flags": "replace",
+            "options": "start=%2%30,title=%12%Demo, part 1",
+        })]);
+        // Nothing is left behind as a global property for the next video
+        assert_eq!(mock.property("start"), None);
+        assert_eq!(mock.property("title"), None);
+    }
+    
+    #[tokio::test]
+    async fn recycled_process_does_not_inherit_per_file_options() {
+        let mock = MockMpv::start();
+        let pool = VideoManagerPool::with_idle(vec![idle_instance(&mock), idle_instance(&mock)], 2, no_refill()).await;
+        
+        pool.play("first.mkv".to_string(), PlaybackOptions {
+            start_time: Some(30.0),
+            ..fast_options()
+        }).await.unwrap();
+        pool.play("second.mkv".to_string(), fast_options()).await.unwrap();
+        
+        let loads = loadfile_commands(&mock);
+        assert_eq!(loads.len(), 2);
+        assert_eq!(loads[1], json!(["loadfile", "second.mkv", "replace"]));
+    }
+}
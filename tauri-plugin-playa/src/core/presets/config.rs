@@ -1,5 +1,8 @@
 use std::collections::HashMap;
-use std::sync::OnceLock;
+use std::fs;
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+use serde::Deserialize;
 use crate::Result;
 use crate::Error;
 
@@ -35,6 +38,8 @@ pub struct SystemInfo {
     pub platform: Platform,
     pub gpu_vendor: GpuVendor,
     pub is_high_end: bool,
+    /// Dedicated video memory in megabytes, when it can be detected
+    pub vram_mb: Option<u64>,
 }
 
 // Define the preset configuration struct
@@ -45,13 +50,26 @@ pub struct PresetConfig {
     pub platform: Option<Platform>,
     pub performance_level: PerformanceLevel,
     pub config_options: HashMap<String, String>,
+    /// Preset whose options this one extends; its own options win on conflicts
+    pub base: Option<String>,
 }
 
-// Global preset registry (initialized on first access)
-static PRESET_REGISTRY: OnceLock<HashMap<String, PresetConfig>> = OnceLock::new();
+// Difference between the option sets of two presets
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PresetDiff {
+    /// Options only set by the second preset, as (key, value)
+    pub added: Vec<(String, String)>,
+    /// Options only set by the first preset, as (key, value)
+    pub removed: Vec<(String, String)>,
+    /// Options set by both with different values, as (key, old value, new value)
+    pub changed: Vec<(String, String, String)>,
+}
+
+// Global preset registry (initialized on first access, extended by user preset files)
+static PRESET_REGISTRY: OnceLock<RwLock<HashMap<String, PresetConfig>>> = OnceLock::new();
 
 // Initialize the preset registry
-fn get_preset_registry() -> &'static HashMap<String, PresetConfig> {
+fn get_preset_registry() -> &'static RwLock<HashMap<String, PresetConfig>> {
     PRESET_REGISTRY.get_or_init(|| {
         let mut presets = HashMap::new();
         
@@ -73,7 +91,7 @@ fn get_preset_registry() -> &'static HashMap<String, PresetConfig> {
         presets.insert("linux-high-quality".to_string(), create_linux_high_quality_preset());
         presets.insert("linux-fast".to_string(), create_linux_fast_preset());
         
-        presets
+        RwLock::new(presets)
     })
 }
 
@@ -81,30 +99,225 @@ fn get_preset_registry() -> &'static HashMap<String, PresetConfig> {
 
 /// Get a list of all available presets
 pub fn list_available_presets() -> Vec<String> {
-    get_preset_registry().keys().cloned().collect()
+    get_preset_registry().read().unwrap().keys().cloned().collect()
+}
+
+/// Check whether a preset can be used on the running platform.
+/// Presets without a platform work everywhere; unknown presets are not compatible.
+pub fn is_preset_compatible(preset_name: &str) -> bool {
+    let platform = super::platform::detection::detect_platform();
+    get_preset_registry().read().unwrap()
+        .get(preset_name)
+        .is_some_and(|preset| is_compatible_with(preset, platform))
+}
+
+/// Get a sorted list of the presets that can be used on the running platform
+pub fn list_compatible_presets() -> Vec<String> {
+    let platform = super::platform::detection::detect_platform();
+    let mut presets: Vec<String> = get_preset_registry().read().unwrap()
+        .values()
+        .filter(|preset| is_compatible_with(preset, platform))
+        .map(|preset| preset.name.clone())
+        .collect();
+    presets.sort();
+    presets
+}
+
+fn is_compatible_with(preset: &PresetConfig, platform: Platform) -> bool {
+    match preset.platform {
+        Some(preset_platform) => preset_platform == platform,
+        None => true,
+    }
 }
 
 /// Get details about a specific preset
-pub fn get_preset_details(preset_name: &str) -> Option<&'static PresetConfig> {
-    get_preset_registry().get(preset_name)
+pub fn get_preset_details(preset_name: &str) -> Option<PresetConfig> {
+    get_preset_registry().read().unwrap().get(preset_name).cloned()
+}
+
+// Layout of a user preset file
+#[derive(Debug, Deserialize)]
+struct PresetFile {
+    #[serde(default)]
+    presets: HashMap<String, PresetFileEntry>,
+}
+
+// A single `[presets.<name>]` table
+#[derive(Debug, Deserialize)]
+struct PresetFileEntry {
+    description: String,
+    platform: Option<String>,
+    performance_level: String,
+    base: Option<String>,
+    #[serde(default)]
+    config_options: HashMap<String, String>,
+}
+
+/// Load user-defined presets from a TOML file into the registry.
+/// Presets with the same name as a built-in replace it. Returns the number loaded.
+pub fn load_presets_from_file(path: &Path) -> Result<usize> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| Error::Io(format!("Failed to read preset file {}: {}", path.display(), e)))?;
+    let file: PresetFile = toml::from_str(&contents)
+        .map_err(|e| Error::ConfigError(format!("Invalid preset file {}: {}", path.display(), e)))?;
+    
+    // Validate every preset before touching the registry so a bad file loads nothing
+    let mut loaded = Vec::with_capacity(file.presets.len());
+    for (name, entry) in file.presets {
+        let platform = match entry.platform.as_deref() {
+            Some(platform) => Some(parse_platform(platform).ok_or_else(|| {
+                Error::ConfigError(format!("Preset '{}' has unknown platform '{}'", name, platform))
+            })?),
+            None => None,
+        };
+        let performance_level = parse_performance_level(&entry.performance_level).ok_or_else(|| {
+            Error::ConfigError(format!(
+                "Preset '{}' has unknown performance_level '{}' (expected fast, balanced or high-quality)",
+                name, entry.performance_level
+            ))
+        })?;
+        
+        loaded.push(PresetConfig {
+            name: name.clone(),
+            description: entry.description,
+            platform,
+            performance_level,
+            config_options: entry.config_options,
+            base: entry.base,
+        });
+    }
+    
+    let count = loaded.len();
+    let mut registry = get_preset_registry().write().unwrap();
+    for preset in loaded {
+        registry.insert(preset.name.clone(), preset);
+    }
+    
+    Ok(count)
+}
+
+fn parse_platform(value: &str) -> Option<Platform> {
+    match value.to_ascii_lowercase().as_str() {
+        "macos" => Some(Platform::MacOS),
+        "windows" => Some(Platform::Windows),
+        "linux" => Some(Platform::Linux),
+        _ => None,
+    }
 }
 
-/// Apply a preset to the mpv configuration
+fn parse_performance_level(value: &str) -> Option<PerformanceLevel> {
+    match value.to_ascii_lowercase().as_str() {
+        "fast" => Some(PerformanceLevel::Fast),
+        "balanced" => Some(PerformanceLevel::Balanced),
+        "high-quality" | "high_quality" | "highquality" => Some(PerformanceLevel::HighQuality),
+        _ => None,
+    }
+}
+
+// Collect the options of a preset and its base chain, the most derived preset winning
+fn resolve_preset_options(
+    registry: &HashMap<String, PresetConfig>,
+    preset_name: &str,
+) -> Result<HashMap<String, String>> {
+    let mut chain: Vec<&PresetConfig> = Vec::new();
+    let mut next = Some(preset_name);
+    
+    while let Some(name) = next {
+        if chain.iter().any(|preset| preset.name == name) {
+            return Err(Error::ConfigError(format!(
+                "preset inheritance cycle detected: {} -> {}",
+                chain.iter().map(|preset| preset.name.as_str()).collect::<Vec<_>>().join(" -> "),
+                name
+            )));
+        }
+        
+        let preset = registry.get(name).ok_or_else(|| match chain.last() {
+            Some(child) => Error::ConfigError(format!("Preset '{}' has unknown base '{}'", child.name, name)),
+            None => Error::ConfigError(format!("Preset '{}' not found", name)),
+        })?;
+        chain.push(preset);
+        next = preset.base.as_deref();
+    }
+    
+    // Start from the root so each child overrides its base
+    let mut options = HashMap::new();
+    for preset in chain.iter().rev() {
+        options.extend(preset.config_options.iter().map(|(key, value)| (key.clone(), value.clone())));
+    }
+    
+    Ok(options)
+}
+
+/// Apply a preset to the mpv configuration, including the options it inherits from its base
 pub fn apply_preset(preset_name: &str) -> Result<Vec<String>> {
-    match get_preset_registry().get(preset_name) {
-        Some(preset) => {
-            // Convert preset to mpv command line arguments
-            let args: Vec<String> = preset.config_options
-                .iter()
-                .map(|(key, value)| format!("--{}={}", key, value))
-                .collect();
-            
-            Ok(args)
-        },
-        None => Err(Error::ConfigError(format!("Preset '{}' not found", preset_name))),
+    let registry = get_preset_registry().read().unwrap();
+    let options = resolve_preset_options(&registry, preset_name)?;
+    
+    // Options for another platform may be rejected by mpv, but let it try
+    let platform = super::platform::detection::detect_platform();
+    if let Some(preset) = registry.get(preset_name) {
+        if !is_compatible_with(preset, platform) {
+            log::warn!(
+                "Preset '{}' is meant for {:?} but is being applied on {:?}",
+                preset_name, preset.platform.unwrap(), platform
+            );
+        }
+    }
+    
+    // Convert preset to mpv command line arguments
+    let args: Vec<String> = options
+        .iter()
+        .map(|(key, value)| format!("--{}={}", key, value))
+        .collect();
+    
+    Ok(args)
+}
+
+/// First mpv release that ships the `gpu-next` video output
+const GPU_NEXT_MIN_MPV_VERSION: &str = "0.35.0";
+
+/// Get the oldest mpv release that supports every option of a preset, if it needs a newer one.
+/// Compare it against `MpvIpcClient::get_mpv_version` to warn about an outdated mpv.
+pub fn min_mpv_version(preset_name: &str) -> Option<&'static str> {
+    let registry = get_preset_registry().read().unwrap();
+    let options = resolve_preset_options(&registry, preset_name).ok()?;
+    
+    match options.get("vo").map(String::as_str) {
+        Some("gpu-next") => Some(GPU_NEXT_MIN_MPV_VERSION),
+        _ => None,
     }
 }
 
+/// Compare the options of two presets, describing what switching from `a` to `b` changes
+pub fn diff_presets(a: &str, b: &str) -> Result<PresetDiff> {
+    let registry = get_preset_registry().read().unwrap();
+    let from = resolve_preset_options(&registry, a)?;
+    let to = resolve_preset_options(&registry, b)?;
+    
+    let mut diff = PresetDiff::default();
+    for (key, old_value) in &from {
+        match to.get(key) {
+            Some(new_value) if new_value != old_value => {
+                diff.changed.push((key.clone(), old_value.clone(), new_value.clone()));
+            },
+            Some(_) => {},
+            None => diff.removed.push((key.clone(), old_value.clone())),
+        }
+    }
+    for (key, new_value) in &to {
+        if !from.contains_key(key) {
+            diff.added.push((key.clone(), new_value.clone()));
+        }
+    }
+    
+    // Keep the output stable for display
+    diff.added.sort();
+    diff.removed.sort();
+    diff.changed.sort();
+    
+    Ok(diff)
+}
+
 /// Get the recommended preset based on the current system
 pub fn get_recommended_preset() -> String {
     let system_info = super::platform::detection::detect_system_info();
@@ -189,6 +402,7 @@ fn create_macos_balanced_preset() -> PresetConfig {
         platform: Some(Platform::MacOS),
         performance_level: PerformanceLevel::Balanced,
         config_options,
+        base: None,
     }
 }
 
@@ -234,6 +448,7 @@ fn create_macos_high_quality_preset() -> PresetConfig {
         platform: Some(Platform::MacOS),
         performance_level: PerformanceLevel::HighQuality,
         config_options,
+        base: None,
     }
 }
 
@@ -267,6 +482,7 @@ fn create_macos_fast_preset() -> PresetConfig {
         platform: Some(Platform::MacOS),
         performance_level: PerformanceLevel::Fast,
         config_options,
+        base: None,
     }
 }
 
@@ -303,6 +519,7 @@ fn create_windows_nvidia_balanced_preset() -> PresetConfig {
         platform: Some(Platform::Windows),
         performance_level: PerformanceLevel::Balanced,
         config_options,
+        base: None,
     }
 }
 
@@ -337,6 +554,7 @@ fn create_windows_amd_balanced_preset() -> PresetConfig {
         platform: Some(Platform::Windows),
         performance_level: PerformanceLevel::Balanced,
         config_options,
+        base: None,
     }
 }
 
@@ -370,6 +588,7 @@ fn create_windows_intel_balanced_preset() -> PresetConfig {
         platform: Some(Platform::Windows),
         performance_level: PerformanceLevel::Balanced,
         config_options,
+        base: None,
     }
 }
 
@@ -410,6 +629,7 @@ fn create_windows_nvidia_high_quality_preset() -> PresetConfig {
         platform: Some(Platform::Windows),
         performance_level: PerformanceLevel::HighQuality,
         config_options,
+        base: None,
     }
 }
 
@@ -449,6 +669,7 @@ fn create_windows_amd_high_quality_preset() -> PresetConfig {
         platform: Some(Platform::Windows),
         performance_level: PerformanceLevel::HighQuality,
         config_options,
+        base: None,
     }
 }
 
@@ -484,6 +705,7 @@ fn create_windows_intel_fast_preset() -> PresetConfig {
         platform: Some(Platform::Windows),
         performance_level: PerformanceLevel::Fast,
         config_options,
+        base: None,
     }
 }
 
@@ -515,6 +737,7 @@ fn create_linux_balanced_preset() -> PresetConfig {
         platform: Some(Platform::Linux),
         performance_level: PerformanceLevel::Balanced,
         config_options,
+        base: None,
     }
 }
 
@@ -550,6 +773,7 @@ fn create_linux_high_quality_preset() -> PresetConfig {
         platform: Some(Platform::Linux),
         performance_level: PerformanceLevel::HighQuality,
         config_options,
+        base: None,
     }
 }
 
@@ -582,5 +806,51 @@ fn create_linux_fast_preset() -> PresetConfig {
         platform: Some(Platform::Linux),
         performance_level: PerformanceLevel::Fast,
         config_options,
+        base: None,
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn diff_shows_scaling_changes_between_macos_presets() {
+        let diff = diff_presets("macos-balanced", "macos-high-quality").unwrap();
+        
+        for (key, old_value, new_value) in [
+            ("scale", "spline36", "ewa_lanczossharp"),
+            ("dscale", "mitchell", "ewa_lanczos"),
+            ("cscale", "spline36", "ewa_lanczos"),
+        ] {
+            assert!(diff.changed.contains(&(key.to_string(), old_value.to_string(), new_value.to_string())), "{} not changed", key);
+        }
+        assert!(diff.added.contains(&("deband".to_string(), "yes".to_string())));
+        assert!(diff.removed.is_empty());
+        assert!(!diff.changed.iter().any(|(key, _, _)| key == "vo"));
+    }
+    
+    #[test]
+    fn diff_rejects_unknown_presets() {
+        assert!(diff_presets("macos-balanced", "no-such-preset").is_err());
+    }
+    
+    #[test]
+    fn min_mpv_version_follows_gpu_next_through_inheritance() {
+        assert_eq!(min_mpv_version("macos-balanced"), Some("0.35.0"));
+        assert_eq!(min_mpv_version("macos-fast"), None);
+        assert_eq!(min_mpv_version("no-such-preset"), None);
+        
+        // A user preset that keeps its base's video output inherits the requirement
+        let name = format!("test-child-{}", uuid::Uuid::new_v4());
+        let path = std::env::temp_dir().join(format!("{}.toml", name));
+        fs::write(&path, format!(
+            "[presets.{}]\ndescription = \"Child\"\nperformance_level = \"fast\"\nbase = \"macos-balanced\"\n\n[presets.{}.config_options]\nscale = \"bilinear\"\n",
+            name, name
+        )).unwrap();
+        let loaded = load_presets_from_file(&path);
+        let _ = fs::remove_file(&path);
+        
+        assert_eq!(loaded.unwrap(), 1);
+        assert_eq!(min_mpv_version(&name), Some("0.35.0"));
+    }
+}
@@ -1,8 +1,9 @@
 mod config;
 mod platform;
 
-pub use config::{Platform, PerformanceLevel, GpuVendor, SystemInfo, PresetConfig};
+pub use config::{Platform, PerformanceLevel, GpuVendor, SystemInfo, PresetConfig, PresetDiff};
 pub use platform::detection::detect_system_info;
+pub use platform::hwdec::{probe_hwdec_support, probe_hwdec_support_with};
 
 // Re-export the public API functions
 pub use config::{
@@ -10,4 +11,9 @@ pub use config::{
     get_preset_details,
     apply_preset,
     get_recommended_preset,
+    load_presets_from_file,
+    diff_presets,
+    min_mpv_version,
+    is_preset_compatible,
+    list_compatible_presets,
 }; 
\ No newline at end of file
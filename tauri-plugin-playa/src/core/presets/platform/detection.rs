@@ -1,19 +1,24 @@
 use crate::core::presets::config::{Platform, GpuVendor, SystemInfo};
 use std::process::Command;
 
+/// Minimum dedicated video memory for a system to be considered high-end
+const HIGH_END_VRAM_MB: u64 = 8192;
+
 pub fn detect_system_info() -> SystemInfo {
     let platform = detect_platform();
     let gpu_vendor = detect_gpu_vendor();
-    let is_high_end = detect_high_end_system();
+    let vram_mb = detect_vram_mb();
+    let is_high_end = detect_high_end_system(vram_mb);
     
     SystemInfo {
         platform,
         gpu_vendor,
         is_high_end,
+        vram_mb,
     }
 }
 
-fn detect_platform() -> Platform {
+pub(crate) fn detect_platform() -> Platform {
     #[cfg(target_os = "macos")]
     return Platform::MacOS;
     
@@ -76,17 +81,87 @@ fn detect_gpu_vendor() -> GpuVendor {
             }
         }
         
-        return GpuVendor::Unknown;
+        // lspci is missing or found nothing, fall back to sysfs
+        return detect_gpu_vendor_sysfs();
     }
     
     #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
     return GpuVendor::Unknown;
 }
 
-fn detect_high_end_system() -> bool {
+/// Returns the device directories of the DRM cards exposed in sysfs
+#[cfg(target_os = "linux")]
+fn drm_card_devices() -> Vec<std::path::PathBuf> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/drm") else {
+        return Vec::new();
+    };
+    
+    let mut devices: Vec<_> = entries
+        .flatten()
+        .filter(|entry| {
+            // Skip connector entries such as card0-HDMI-A-1
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("card") && !name.contains('-')
+        })
+        .map(|entry| entry.path().join("device"))
+        .collect();
+    devices.sort();
+    devices
+}
+
+/// Detects the GPU vendor from the PCI vendor IDs in sysfs
+#[cfg(target_os = "linux")]
+fn detect_gpu_vendor_sysfs() -> GpuVendor {
+    let mut fallback = GpuVendor::Unknown;
+    
+    for device in drm_card_devices() {
+        let Ok(vendor) = std::fs::read_to_string(device.join("vendor")) else {
+            continue;
+        };
+        
+        let vendor = match vendor.trim().to_lowercase().as_str() {
+            "0x10de" => GpuVendor::Nvidia,
+            "0x1002" => GpuVendor::AMD,
+            "0x8086" => GpuVendor::Intel,
+            _ => continue,
+        };
+        
+        // Prefer a discrete GPU over integrated Intel graphics
+        if vendor != GpuVendor::Intel {
+            return vendor;
+        }
+        fallback = vendor;
+    }
+    
+    fallback
+}
+
+/// Detects the dedicated video memory in megabytes
+fn detect_vram_mb() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        // Only exposed by some drivers, such as amdgpu
+        return drm_card_devices()
+            .iter()
+            .filter_map(|device| std::fs::read_to_string(device.join("mem_info_vram_total")).ok())
+            .filter_map(|bytes| bytes.trim().parse::<u64>().ok())
+            .map(|bytes| bytes / (1024 * 1024))
+            .max();
+    }
+    
+    #[cfg(not(target_os = "linux"))]
+    return None;
+}
+
+fn detect_high_end_system(vram_mb: Option<u64>) -> bool {
     // This is a simplified implementation
     // In a real-world scenario, we would check more system parameters
     
+    if vram_mb.is_some_and(|vram| vram >= HIGH_END_VRAM_MB) {
+        return true;
+    }
+    
     #[cfg(target_os = "macos")]
     {
         // Check if we're on Apple Silicon M1 Pro/Max or M2/M3
@@ -0,0 +1,169 @@
+use crate::{Error, Result};
+use crate::core::config::ipc::{BackoffStrategy, IpcConfig};
+use crate::core::player::ipc::MpvIpcClient;
+use crate::core::player::process::generate_tagged_socket_path;
+use log::debug;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+// Cache of probe results keyed by mpv binary and codec name
+static HWDEC_CACHE: OnceLock<Mutex<HashMap<(PathBuf, String), bool>>> = OnceLock::new();
+
+/// Test pattern used as the source for probe samples
+const PROBE_SOURCE: &str = "av://lavfi:testsrc2=duration=1:size=640x360:rate=24";
+
+/// How long the probe waits for mpv to report the decoder it picked
+const PROBE_TIMEOUT_MS: u64 = 5000;
+
+/// Returns the encoder mpv should use to produce a sample for the given codec
+fn encoder_for_codec(codec: &str) -> Option<&'static str> {
+    match codec {
+        "h264" | "avc" => Some("libx264"),
+        "hevc" | "h265" => Some("libx265"),
+        "vp8" => Some("libvpx"),
+        "vp9" => Some("libvpx-vp9"),
+        "av1" => Some("libaom-av1"),
+        "mpeg2" | "mpeg2video" => Some("mpeg2video"),
+        _ => None,
+    }
+}
+
+/// Probes whether hardware decoding works for a codec on this machine, using mpv from PATH.
+/// See [`probe_hwdec_support_with`].
+pub fn probe_hwdec_support(codec: &str) -> Result<bool> {
+    probe_hwdec_support_with(codec, None)
+}
+
+/// Probes whether hardware decoding works for a codec with the given mpv binary.
+/// Encodes a tiny test pattern with the codec, then decodes a single frame with
+/// `--hwdec=auto --vo=null` and reads mpv's `hwdec-current` property over IPC.
+/// Results are cached per binary and codec for the lifetime of the process.
+pub fn probe_hwdec_support_with(codec: &str, mpv_path: Option<&Path>) -> Result<bool> {
+    let codec = codec.to_lowercase();
+    let mpv_binary = mpv_path.map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from("mpv"));
+    let cache_key = (mpv_binary.clone(), codec.clone());
+    let cache = HWDEC_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    
+    if let Some(supported) = cache.lock().unwrap().get(&cache_key) {
+        return Ok(*supported);
+    }
+    
+    let encoder = encoder_for_codec(&codec)
+        .ok_or_else(|| Error::ConfigError(format!("Unsupported codec for hwdec probe: {}", codec)))?;
+    if mpv_path.is_some() && !mpv_binary.exists() {
+        return Err(Error::ConfigError(format!("mpv binary not found at {}", mpv_binary.display())));
+    }
+    
+    let sample_path = std::env::temp_dir().join(format!("mpv-hwdec-probe-{}-{}.mkv", codec, Uuid::new_v4()));
+    debug!("Encoding hwdec probe sample for {} at {}", codec, sample_path.display());
+    
+    // Encode a short sample with the requested codec
+    let status = Command::new(&mpv_binary)
+        .args([
+            "--no-config",
+            "--really-quiet",
+            PROBE_SOURCE,
+            &format!("--o={}", sample_path.display()),
+            "--of=matroska",
+            &format!("--ovc={}", encoder),
+        ])
+        .status()?;
+    
+    if !status.success() {
+        let _ = std::fs::remove_file(&sample_path);
+        return Err(Error::MpvError(format!("Failed to encode hwdec probe sample with {}", encoder)));
+    }
+    
+    let result = decode_with_hwdec(&mpv_binary, &sample_path);
+    let _ = std::fs::remove_file(&sample_path);
+    let supported = result?;
+    
+    debug!("Hardware decoding for {} supported: {}", codec, supported);
+    cache.lock().unwrap().insert(cache_key, supported);
+    
+    Ok(supported)
+}
+
+/// Decodes the first frame of a sample without any output and asks mpv which decoder it used
+fn decode_with_hwdec(mpv_binary: &Path, sample_path: &Path) -> Result<bool> {
+    let socket_path = generate_tagged_socket_path(Some("hwdec-probe"));
+    
+    // keep-open holds mpv on the decoded frame, so the property can still be read
+    let mut process = Command::new(mpv_binary)
+        .args([
+            "--no-config",
+            "--hwdec=auto",
+            "--vo=null",
+            "--ao=null",
+            "--no-audio",
+            "--frames=1",
+            "--keep-open=yes",
+            &format!("--input-ipc-server={}", socket_path),
+        ])
+        .arg(sample_path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    
+    let result = read_hwdec_current(&socket_path);
+    
+    let _ = process.kill();
+    let _ = process.wait();
+    #[cfg(target_family = "unix")]
+    {
+        let _ = std::fs::remove_file(&socket_path);
+    }
+    
+    let hwdec = result?;
+    debug!("hwdec-current for probe sample: {}", hwdec);
+    Ok(!hwdec.is_empty() && hwdec != "no")
+}
+
+/// Waits for mpv to initialize its decoder and returns the `hwdec-current` value
+fn read_hwdec_current(socket_path: &str) -> Result<String> {
+    let config = IpcConfig::new(1000, 100, false, 25, 100).with_backoff(BackoffStrategy::Fixed);
+    let mut client = MpvIpcClient::connect_with_config(socket_path, config)?;
+    
+    // The property is unavailable until the decoder has been initialized
+    let deadline = Instant::now() + Duration::from_millis(PROBE_TIMEOUT_MS);
+    let result = loop {
+        match client.get_property("hwdec-current") {
+            Ok(value) => break Ok(value.as_str().unwrap_or_default().to_string()),
+            Err(Error::MpvError(_)) if Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            Err(e) => break Err(e),
+        }
+    };
+    
+    let _ = client.quit();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn unknown_codec_is_rejected_before_running_mpv() {
+        let result = probe_hwdec_support_with("theora", Some(Path::new("/nonexistent/mpv")));
+        assert!(matches!(result, Err(Error::ConfigError(message)) if message.contains("Unsupported codec")));
+    }
+    
+    #[test]
+    fn missing_mpv_binary_is_reported() {
+        let result = probe_hwdec_support_with("h264", Some(Path::new("/nonexistent/mpv")));
+        assert!(matches!(result, Err(Error::ConfigError(message)) if message.contains("mpv binary not found")));
+    }
+    
+    #[test]
+    #[ignore = "requires mpv with libx264"]
+    fn probes_h264_and_caches_the_result() {
+        let supported = probe_hwdec_support("h264").unwrap();
+        assert_eq!(probe_hwdec_support("H264").unwrap(), supported);
+    }
+}
@@ -1 +1,2 @@
-pub mod detection; 
\ No newline at end of file
+pub mod detection;
+pub mod hwdec;
\ No newline at end of file
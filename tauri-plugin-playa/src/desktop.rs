@@ -19,6 +19,8 @@ pub struct Playa<R: Runtime> {
     pub app_handle: AppHandle<R>,
     pub video_manager: Arc<Mutex<VideoManager>>,
     pub asset_path: PathBuf,
+    /// Runtime shared by event forwarding and shutdown cleanup
+    pub runtime: Arc<tokio::runtime::Runtime>,
 }
 
 impl<R: Runtime> Playa<R> {
@@ -165,6 +167,12 @@ impl<R: Runtime> Playa<R> {
     pub fn get_assets_path(&self) -> PathBuf {
         self.asset_path.clone()
     }
+    
+    /// Close all videos on the plugin runtime, waiting briefly for the video manager if it is busy
+    pub fn shutdown(&self) -> Result<()> {
+        VideoManager::shutdown_shared(&self.video_manager, &self.runtime)
+            .map_err(|e| Error::PluginError(format!("Failed to close videos during shutdown: {}", e)))
+    }
 }
 
 /// Initialize the playa plugin
@@ -175,12 +183,12 @@ pub fn init<R: Runtime>(app: &AppHandle<R>, _api: PluginApi<R, ()>) -> Result<Pl
         .try_init()
         .ok();
     
-    // Create a new tokio runtime for the plugin
-    let rt = tokio::runtime::Runtime::new()
-        .map_err(|e| Error::PluginError(format!("Failed to create Tokio runtime: {}", e)))?;
+    // Create the tokio runtime shared by the whole plugin
+    let runtime = Arc::new(tokio::runtime::Runtime::new()
+        .map_err(|e| Error::PluginError(format!("Failed to create Tokio runtime: {}", e)))?);
     
     // Create a video manager
-    let video_manager = rt.block_on(async {
+    let video_manager = runtime.block_on(async {
         VideoManager::new()
     });
     
@@ -197,10 +205,11 @@ pub fn init<R: Runtime>(app: &AppHandle<R>, _api: PluginApi<R, ()>) -> Result<Pl
         app_handle: app.clone(),
         video_manager: Arc::new(Mutex::new(video_manager)),
         asset_path,
+        runtime,
     };
     
     // Set up event subscription to forward events to the frontend
-    setup_event_subscription(app, Arc::clone(&playa.video_manager))?;
+    setup_event_subscription(app, &playa.runtime, Arc::clone(&playa.video_manager))?;
     
     Ok(playa)
 }
@@ -208,12 +217,13 @@ pub fn init<R: Runtime>(app: &AppHandle<R>, _api: PluginApi<R, ()>) -> Result<Pl
 /// Set up event subscription to forward events to the frontend
 fn setup_event_subscription<R: Runtime>(
     app: &AppHandle<R>,
+    runtime: &tokio::runtime::Runtime,
     video_manager: Arc<Mutex<VideoManager>>,
 ) -> Result<()> {
     let app_handle = app.clone();
     
     // Spawn a task to handle video events
-    tokio::spawn(async move {
+    runtime.spawn(async move {
         let mut subscription = video_manager.lock().await.deref().subscribe().await;
         
         while let Some(event) = subscription.recv().await {
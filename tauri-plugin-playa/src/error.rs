@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: MIT
 
 use serde::{Serialize, Deserialize};
+use std::fmt;
 use std::io;
 
 #[derive(Debug, thiserror::Error, Serialize, Deserialize)]
@@ -25,6 +26,72 @@ pub enum Error {
     
     #[error("Plugin error: {0}")]
     PluginError(String),
+    
+    #[error("Timed out connecting to mpv at {socket_path} after {attempts} attempts")]
+    ConnectionTimeout { socket_path: String, attempts: u32 },
+    
+    #[error("Timed out after {timeout_ms}ms waiting for a matching event")]
+    EventTimeout { timeout_ms: u64 },
+    
+    #[error("Failed to spawn mpv while {}: {}", .0.stage, .0.cause)]
+    SpawnFailed(Box<SpawnDiagnostics>),
+}
+
+/// The step of the spawn path that failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpawnStage {
+    /// Locating the mpv binary
+    ResolveBinary,
+    /// Building the mpv argument list
+    BuildArgs,
+    /// Starting the mpv process
+    Launch,
+    /// Waiting for mpv to create its IPC socket
+    SocketCreate,
+    /// Applying permissions to the IPC socket
+    SocketPermissions,
+}
+
+impl fmt::Display for SpawnStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stage = match self {
+            SpawnStage::ResolveBinary => "resolving the mpv binary",
+            SpawnStage::BuildArgs => "building arguments",
+            SpawnStage::Launch => "launching the process",
+            SpawnStage::SocketCreate => "waiting for the IPC socket",
+            SpawnStage::SocketPermissions => "setting IPC socket permissions",
+        };
+        f.write_str(stage)
+    }
+}
+
+/// Everything known about a failed mpv spawn
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpawnDiagnostics {
+    /// The mpv binary that was used or looked for
+    pub program: String,
+    /// The arguments mpv was started with, empty if the failure came before they were built
+    pub args: Vec<String>,
+    /// Warnings collected while validating the config files
+    pub config_warnings: Vec<String>,
+    /// The last lines mpv printed, when spawned with `capture_output`
+    #[serde(default)]
+    pub output: Vec<String>,
+    /// The step that failed
+    pub stage: SpawnStage,
+    /// The underlying error message
+    pub cause: String,
+}
+
+impl SpawnDiagnostics {
+    /// Returns the attempted command line, for bug reports
+    pub fn command_line(&self) -> String {
+        std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 }
 
 impl From<io::Error> for Error {
@@ -15,7 +15,7 @@ mod error;
 mod models;
 pub mod core;
 
-pub use error::{Error, Result};
+pub use error::{Error, Result, SpawnDiagnostics, SpawnStage};
 
 #[cfg(desktop)]
 use desktop::Playa;
@@ -59,20 +59,9 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             // Using try_state to avoid panicking if the state is not available
             log::info!("Cleaning up playa plugin resources");
             
-            // We can perform any necessary cleanup here
-            // For example, ensure all videos are closed
-            let video_manager = playa.inner().video_manager.clone();
-            
-            // Use a blocking runtime to ensure cleanup completes before app exit
-            if let Ok(rt) = tokio::runtime::Runtime::new() {
-              rt.block_on(async {
-                if let Ok(manager) = video_manager.try_lock() {
-                  // Close all videos
-                  if let Err(e) = manager.close_all().await {
-                    log::error!("Error closing videos during shutdown: {}", e);
-                  }
-                }
-              });
+            // Close all videos on the plugin's own runtime before the app exits
+            if let Err(e) = playa.inner().shutdown() {
+              log::error!("Error closing videos during shutdown: {}", e);
             }
           }
         }